@@ -0,0 +1,243 @@
+use crate::solution::{self, Day, Input};
+use std::error;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+pub const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+// Collected run times for a single phase (parsing, part 1 or part 2) of a single day
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PhaseTimings {
+    samples: Vec<Duration>,
+}
+
+impl PhaseTimings {
+    pub fn new(samples: Vec<Duration>) -> Self {
+        let mut samples = samples;
+        samples.sort();
+
+        PhaseTimings { samples }
+    }
+
+    pub fn min(&self) -> Option<Duration> {
+        self.samples.first().copied()
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.samples.last().copied()
+    }
+
+    pub fn median(&self) -> Option<Duration> {
+        let len = self.samples.len();
+
+        match len {
+            0 => None,
+            _ if len % 2 == 1 => Some(self.samples[len / 2]),
+            _ => Some((self.samples[len / 2 - 1] + self.samples[len / 2]) / 2),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DayBenchmark {
+    pub day: u32,
+    // Timings for each of `PHASES`; phases that fail (like day 25's nonexistent part 2) are `None`
+    pub phases: [Option<PhaseTimings>; 3],
+    // Whether the day works out both answers while parsing, in which case the parsing time covers
+    // the whole day and the parts aren't timed at all
+    pub solved_in_parse: bool,
+}
+
+// Parses the given input and solves both parts `iterations` times, timing each phase separately.
+// Parsing failures abort the benchmark, but a part that fails is just left out of the results, as
+// are both parts of days that solve them while parsing.
+pub fn benchmark(
+    day: &Day,
+    input: &Input,
+    iterations: usize,
+) -> Result<DayBenchmark, Box<dyn error::Error>> {
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+    let mut failed = [false; 3];
+    let mut solved_in_parse = false;
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let solution = day.parse(input)?;
+        samples[0].push(start.elapsed());
+
+        if solution.solved_in_parse() {
+            solved_in_parse = true;
+            failed[1..].fill(true);
+        }
+
+        for phase in 1..=2 {
+            if failed[phase] {
+                continue;
+            }
+
+            let start = Instant::now();
+
+            if solution::solve(solution.as_ref(), phase as u32).is_ok() {
+                samples[phase].push(start.elapsed());
+            } else {
+                failed[phase] = true;
+            }
+        }
+    }
+
+    let mut phases: [Option<PhaseTimings>; 3] = Default::default();
+
+    for (phase, samples) in samples.into_iter().enumerate() {
+        if !failed[phase] {
+            phases[phase] = Some(PhaseTimings::new(samples));
+        }
+    }
+
+    Ok(DayBenchmark {
+        day: day.number,
+        phases,
+        solved_in_parse,
+    })
+}
+
+// Renders benchmark results as JSON. Durations are reported in nanoseconds.
+pub fn to_json(iterations: usize, benchmarks: &[DayBenchmark]) -> String {
+    let mut json = String::new();
+
+    write!(json, "{{\"iterations\":{},\"days\":[", iterations).unwrap();
+
+    for (i, benchmark) in benchmarks.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+
+        write!(json, "{{\"day\":{},", benchmark.day).unwrap();
+
+        if benchmark.solved_in_parse {
+            json.push_str("\"solved_in_parse\":true,");
+        }
+
+        json.push_str("\"phases\":{");
+
+        let phases = PHASES
+            .iter()
+            .zip(benchmark.phases.iter())
+            .filter_map(|(name, timings)| timings.as_ref().map(|timings| (name, timings)));
+
+        for (j, (name, timings)) in phases.enumerate() {
+            if j > 0 {
+                json.push(',');
+            }
+
+            write!(
+                json,
+                "\"{}\":{{\"min_ns\":{},\"median_ns\":{},\"max_ns\":{}}}",
+                name,
+                nanos(timings.min()),
+                nanos(timings.median()),
+                nanos(timings.max())
+            )
+            .unwrap();
+        }
+
+        json.push_str("}}");
+    }
+
+    json.push_str("]}");
+    json
+}
+
+fn nanos(duration: Option<Duration>) -> String {
+    match duration {
+        Some(duration) => duration.as_nanos().to_string(),
+        None => String::from("null"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_phase_timings() {
+        let timings = PhaseTimings::new(vec![
+            Duration::from_millis(7),
+            Duration::from_millis(3),
+            Duration::from_millis(5),
+        ]);
+
+        assert_eq!(Some(Duration::from_millis(3)), timings.min());
+        assert_eq!(Some(Duration::from_millis(5)), timings.median());
+        assert_eq!(Some(Duration::from_millis(7)), timings.max());
+
+        let timings = PhaseTimings::new(vec![
+            Duration::from_millis(2),
+            Duration::from_millis(8),
+            Duration::from_millis(4),
+            Duration::from_millis(6),
+        ]);
+
+        assert_eq!(Some(Duration::from_millis(5)), timings.median());
+        assert_eq!(None, PhaseTimings::default().median());
+    }
+
+    #[test]
+    fn test_benchmark() {
+        let day = solution::day(25).unwrap();
+        let input = Input::new(vec![String::from("..\n..\n")]);
+
+        let benchmark = benchmark(day, &input, 3).unwrap();
+
+        assert_eq!(25, benchmark.day);
+        assert_eq!(3, benchmark.phases[0].as_ref().unwrap().samples.len());
+        assert_eq!(3, benchmark.phases[1].as_ref().unwrap().samples.len());
+        assert!(benchmark.phases[2].is_none());
+        assert!(!benchmark.solved_in_parse);
+
+        // Day 1 streams its input and works out both answers while parsing it
+        let day = solution::day(1).unwrap();
+        let input = Input::new(vec![String::from("1\n3\n2\n4\n")]);
+
+        let streamed = super::benchmark(day, &input, 3).unwrap();
+
+        assert!(streamed.solved_in_parse);
+        assert_eq!(3, streamed.phases[0].as_ref().unwrap().samples.len());
+        assert!(streamed.phases[1].is_none());
+        assert!(streamed.phases[2].is_none());
+    }
+
+    #[test]
+    fn test_to_json() {
+        let benchmark = DayBenchmark {
+            day: 1,
+            phases: [
+                Some(PhaseTimings::new(vec![
+                    Duration::from_nanos(10),
+                    Duration::from_nanos(30),
+                ])),
+                Some(PhaseTimings::new(vec![Duration::from_nanos(5)])),
+                None,
+            ],
+            solved_in_parse: false,
+        };
+
+        let streamed = DayBenchmark {
+            day: 2,
+            phases: [Some(PhaseTimings::new(vec![Duration::from_nanos(7)])), None, None],
+            solved_in_parse: true,
+        };
+
+        assert_eq!(
+            "{\"iterations\":1,\"days\":[{\"day\":2,\"solved_in_parse\":true,\"phases\":{\
+                \"parse\":{\"min_ns\":7,\"median_ns\":7,\"max_ns\":7}}}]}",
+            to_json(1, &[streamed])
+        );
+
+        assert_eq!(
+            "{\"iterations\":2,\"days\":[{\"day\":1,\"phases\":{\
+                \"parse\":{\"min_ns\":10,\"median_ns\":20,\"max_ns\":30},\
+                \"part1\":{\"min_ns\":5,\"median_ns\":5,\"max_ns\":5}}}]}",
+            to_json(2, &[benchmark])
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use advent_of_code_2021::benchmark::{self, DayBenchmark, PHASES};
+use advent_of_code_2021::day16::compiler;
+use advent_of_code_2021::day24::alu::{self, ArithmeticLogicUnit, Register};
+use advent_of_code_2021::day24::symbolic::SymbolicProgram;
+use advent_of_code_2021::solution::{self, Day, InputKind};
+use std::str::FromStr;
+use std::{env, error, fs, io, process};
+
+const USAGE: &str = "Usage:
+    aoc run --day DAY [--part PART] [INPUT...]
+    aoc run --all [--data DATA_DIR]
+    aoc bench --day DAY [--iterations N] [--json OUTPUT_PATH] [INPUT...]
+    aoc bench --all [--data DATA_DIR] [--iterations N] [--json OUTPUT_PATH]
+    aoc alu [--symbolic] PROGRAM_PATH
+    aoc bits EXPRESSION";
+
+// A day to run along with its input values (file paths or literal arguments)
+type DayAndInputs = (&'static Day, Vec<String>);
+
+struct Options {
+    day: Option<u32>,
+    part: Option<u32>,
+    all: bool,
+    data_dir: String,
+    iterations: usize,
+    json_path: Option<String>,
+    inputs: Vec<String>,
+}
+
+impl Options {
+    fn from_args(args: &[String]) -> Result<Self, Box<dyn error::Error>> {
+        let mut options = Options {
+            day: None,
+            part: None,
+            all: false,
+            data_dir: String::from("data"),
+            iterations: 10,
+            json_path: None,
+            inputs: Vec::new(),
+        };
+
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" => options.day = Some(args.next().ok_or(USAGE)?.parse()?),
+                "--part" => options.part = Some(args.next().ok_or(USAGE)?.parse()?),
+                "--all" => options.all = true,
+                "--data" => options.data_dir = args.next().ok_or(USAGE)?.clone(),
+                "--iterations" => options.iterations = args.next().ok_or(USAGE)?.parse()?,
+                "--json" => options.json_path = Some(args.next().ok_or(USAGE)?.clone()),
+                _ => options.inputs.push(arg.clone()),
+            }
+        }
+
+        if options.all == options.day.is_some() {
+            return Err(USAGE.into());
+        }
+
+        if !matches!(options.part, None | Some(1) | Some(2)) {
+            return Err("Part must be 1 or 2".into());
+        }
+
+        if options.iterations == 0 {
+            return Err("Iterations must be at least 1".into());
+        }
+
+        Ok(options)
+    }
+
+    fn parts(&self) -> Vec<u32> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        }
+    }
+
+    // Returns the selected days along with the input values for each, skipping (with a note) any
+    // days whose input can't be found on its own when running everything.
+    fn days_and_inputs(&self) -> Result<Vec<DayAndInputs>, Box<dyn error::Error>> {
+        if self.all {
+            Ok(solution::DAYS
+                .iter()
+                .filter_map(|day| match day.default_paths(&self.data_dir) {
+                    Some(paths) => Some((day, paths)),
+                    None => {
+                        eprintln!(
+                            "day{:02}: skipped; use --day {} {}",
+                            day.number,
+                            day.number,
+                            day.usage()
+                        );
+                        None
+                    }
+                })
+                .collect())
+        } else {
+            let number = self.day.ok_or(USAGE)?;
+            let day =
+                solution::day(number).ok_or_else(|| format!("No solution for day {}", number))?;
+
+            let expected_inputs = match day.input_kind {
+                InputKind::File => 1,
+                InputKind::Files(suffixes) => suffixes.len(),
+                InputKind::Arguments(names) => names.len(),
+            };
+
+            // Days with a single input file read standard input if that file is left out
+            if day.input_kind == InputKind::File && self.inputs.is_empty() {
+                return Ok(vec![(day, vec![String::from(solution::STDIN_PATH)])]);
+            }
+
+            if self.inputs.len() != expected_inputs {
+                return Err(format!("Usage: aoc COMMAND --day {} {}", number, day.usage()).into());
+            }
+
+            Ok(vec![(day, self.inputs.clone())])
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("run") => Options::from_args(&args[1..]).and_then(|options| run(&options)),
+        Some("bench") => Options::from_args(&args[1..]).and_then(|options| bench(&options)),
+        Some("alu") => alu(&args[1..]),
+        Some("bits") => bits(&args[1..]),
+        _ => Err(USAGE.into()),
+    };
+
+    // Report errors with their `Display` representation so parse errors read as diagnostics
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+// Runs every selected day, carrying on past failures (which have already been reported by the
+// time we get to the end) so that one broken day doesn't hide the rest
+fn run(options: &Options) -> Result<(), Box<dyn error::Error>> {
+    let mut failures = 0;
+
+    for (day, inputs) in options.days_and_inputs()? {
+        match run_day(day, &options.parts(), &inputs) {
+            Ok(failed_parts) => failures += failed_parts,
+            Err(error) if options.all => {
+                eprintln!("day{:02}: {}", day.number, error);
+                failures += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        1 => Err("1 day or part failed".into()),
+        _ => Err(format!("{} days or parts failed", failures).into()),
+    }
+}
+
+// Prints each part's answer (or error), returning the number of parts that failed
+fn run_day(day: &Day, parts: &[u32], inputs: &[String]) -> Result<usize, Box<dyn error::Error>> {
+    let solution = day.parse(&day.read_input(inputs)?)?;
+    let mut failed_parts = 0;
+
+    for &part in parts {
+        match solution::solve(solution.as_ref(), part) {
+            // Some answers (like day 13's folded page) span several lines and read better on their
+            // own
+            Ok(answer) if answer.contains('\n') => {
+                println!("day{:02} part {}:\n{}", day.number, part, answer)
+            }
+            Ok(answer) => println!("day{:02} part {}: {}", day.number, part, answer),
+            Err(error) => {
+                eprintln!("day{:02} part {}: {}", day.number, part, error);
+                failed_parts += 1;
+            }
+        }
+    }
+
+    Ok(failed_parts)
+}
+
+fn bench(options: &Options) -> Result<(), Box<dyn error::Error>> {
+    let mut benchmarks = Vec::new();
+
+    for (day, inputs) in options.days_and_inputs()? {
+        // Each iteration parses the input again, so it needs to be in memory even if it came from
+        // a file or standard input
+        let result = day.read_input(&inputs).and_then(|input| {
+            input.buffer()?;
+            benchmark::benchmark(day, &input, options.iterations)
+        });
+
+        match result {
+            Ok(benchmark) => {
+                print_benchmark(&benchmark);
+                benchmarks.push(benchmark);
+            }
+            Err(error) if options.all => eprintln!("day{:02}: {}", day.number, error),
+            Err(error) => return Err(error),
+        }
+    }
+
+    if let Some(path) = &options.json_path {
+        fs::write(path, benchmark::to_json(options.iterations, &benchmarks))?;
+    }
+
+    Ok(())
+}
+
+fn print_benchmark(benchmark: &DayBenchmark) {
+    for (phase, timings) in PHASES.iter().zip(benchmark.phases.iter()) {
+        if let Some(timings) = timings {
+            println!(
+                "day{:02} {:<5}  min {:>12?}  median {:>12?}  max {:>12?}{}",
+                benchmark.day,
+                phase,
+                timings.min().unwrap_or_default(),
+                timings.median().unwrap_or_default(),
+                timings.max().unwrap_or_default(),
+                if benchmark.solved_in_parse { "  (solves both parts)" } else { "" }
+            );
+        }
+    }
+}
+
+// Starts an interactive session with an ALU program (like day 24's MONAD), reading commands from
+// standard input, or with `--symbolic`, prints what the program leaves in `z` in terms of its
+// inputs
+fn alu(args: &[String]) -> Result<(), Box<dyn error::Error>> {
+    let (path, symbolic) = match args {
+        [path] => (path, false),
+        [flag, path] if flag == "--symbolic" => (path, true),
+        _ => return Err(USAGE.into()),
+    };
+
+    let program = ArithmeticLogicUnit::from_str(&fs::read_to_string(path)?)?;
+
+    if symbolic {
+        println!("{}", SymbolicProgram::from_program(&program)?.describe(Register::Z));
+    } else {
+        alu::repl(&program, io::stdin().lock(), io::stdout())?;
+    }
+
+    Ok(())
+}
+
+// Compiles an arithmetic expression (like "1 + 2 * 3") into a BITS transmission for day 16
+fn bits(args: &[String]) -> Result<(), Box<dyn error::Error>> {
+    let expression = match args {
+        [expression] => expression,
+        _ => return Err(USAGE.into()),
+    };
+
+    println!("{}", compiler::compile_to_hex(expression, None)?);
+
+    Ok(())
+}
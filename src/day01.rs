@@ -0,0 +1,126 @@
+use crate::parse_error::ParseError;
+use crate::solution::{Input, Solution};
+use std::collections::VecDeque;
+use std::error;
+pub fn get_increase_count(measurements: &[u32], window_size: usize) -> u32 {
+    // It turns out that we don't actually need to sum the values in the given window; the change
+    // from one position to the next will always be +newValue, -oldValue, and so we can get the same
+    // result (is this an increase or not?) just by compairing the new value coming into the window
+    // with the old value leaving the window.
+    measurements[..=measurements.len() - window_size].iter()
+        .zip(measurements[window_size..].iter())
+        .filter(|(a, b)| b > a)
+        .count() as u32
+}
+
+// Counts increases in a stream of measurements as they arrive, keeping only as many measurements
+// as it needs to see the oldest one leave its window.
+pub struct IncreaseCounter {
+    window_size: usize,
+    window: VecDeque<u32>,
+    increases: u32,
+}
+
+impl IncreaseCounter {
+    pub fn new(window_size: usize) -> Self {
+        IncreaseCounter {
+            window_size,
+            window: VecDeque::with_capacity(window_size + 1),
+            increases: 0,
+        }
+    }
+
+    pub fn add(&mut self, measurement: u32) {
+        self.window.push_back(measurement);
+
+        if self.window.len() > self.window_size {
+            if let Some(oldest) = self.window.pop_front() {
+                if measurement > oldest {
+                    self.increases += 1;
+                }
+            }
+        }
+    }
+
+    pub fn increases(&self) -> u32 {
+        self.increases
+    }
+}
+
+pub struct Day01 {
+    increases: [u32; 2],
+}
+
+impl Solution for Day01 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        let mut counters = [IncreaseCounter::new(1), IncreaseCounter::new(3)];
+
+        for (i, line) in input.lines(0)?.enumerate() {
+            let line = line?;
+            let measurement = line
+                .parse()
+                .map_err(|_| ParseError::at(1, &line, &line, "a measurement").on_line(i + 1))?;
+
+            counters
+                .iter_mut()
+                .for_each(|counter| counter.add(measurement));
+        }
+
+        Ok(Day01 {
+            increases: counters.map(|counter| counter.increases()),
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.increases[0].to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.increases[1].to_string())
+    }
+
+    // Both increase counts are tallied as the measurements stream in
+    fn solved_in_parse(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_increase_count() {
+        let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        assert_eq!(7, get_increase_count(&depths, 1));
+        assert_eq!(5, get_increase_count(&depths, 3));
+    }
+
+    #[test]
+    fn test_increase_counter() {
+        let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+        for (window_size, expected) in [(1, 7), (3, 5)] {
+            let mut counter = IncreaseCounter::new(window_size);
+            depths.iter().for_each(|&depth| counter.add(depth));
+
+            assert_eq!(expected, counter.increases());
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let day = Day01::parse(&Input::new(vec![String::from("199\n200\n208\n210\n")])).unwrap();
+        assert_eq!("3", day.part1().unwrap());
+        assert!(day.solved_in_parse());
+
+        let error = Day01::parse(&Input::new(vec![String::from("199\n200\nx208\n")]))
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            Some(&ParseError::new(1, 3, 1, "a measurement")),
+            error.downcast_ref::<ParseError>()
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,145 @@
+use crate::parse_error::ParseError;
+use crate::solution::{Input, Solution};
+use std::error;
+use self::Command::*;
+
+pub fn get_distance(commands: &[Command]) -> (u32, u32) {
+    commands.iter().fold((0, 0), apply_command)
+}
+
+pub fn get_distance_with_aim(commands: &[Command]) -> (u32, u32) {
+    let (horizontal, depth, _) = commands.iter().fold((0, 0, 0), apply_command_with_aim);
+    (horizontal, depth)
+}
+
+pub fn apply_command((horizontal, depth): (u32, u32), command: &Command) -> (u32, u32) {
+    match command {
+        Forward(h) => (horizontal + h, depth),
+        Down(d) => (horizontal, depth + d),
+        Up(d) => (horizontal, depth - d),
+    }
+}
+
+pub fn apply_command_with_aim(
+    (horizontal, depth, aim): (u32, u32, u32),
+    command: &Command,
+) -> (u32, u32, u32) {
+    match command {
+        Forward(h) => (horizontal + h, depth + aim * h, aim),
+        Down(a) => (horizontal, depth, aim + a),
+        Up(a) => (horizontal, depth, aim - a),
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Command {
+    Forward(u32),
+    Up(u32),
+    Down(u32)
+}
+
+impl TryFrom<&str> for Command {
+    type Error = ParseError;
+
+    fn try_from(command: &str) -> Result<Self, Self::Error> {
+        let (direction, magnitude) = command
+            .split_once(' ')
+            .ok_or_else(|| ParseError::at_end(2, command, "a space followed by a magnitude"))?;
+
+        let magnitude = magnitude
+            .parse::<u32>()
+            .map_err(|_| ParseError::at(2, command, magnitude, "a magnitude"))?;
+
+        match direction {
+            "forward" => Ok(Forward(magnitude)),
+            "down" => Ok(Down(magnitude)),
+            "up" => Ok(Up(magnitude)),
+            _ => Err(ParseError::at(2, command, direction, "\"forward\", \"down\" or \"up\"")),
+        }
+    }
+}
+
+pub struct Day02 {
+    distance: (u32, u32),
+    distance_with_aim: (u32, u32, u32),
+}
+
+impl Solution for Day02 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        let mut distance = (0, 0);
+        let mut distance_with_aim = (0, 0, 0);
+
+        for (i, line) in input.lines(0)?.enumerate() {
+            let command =
+                Command::try_from(line?.as_str()).map_err(|error| error.on_line(i + 1))?;
+
+            distance = apply_command(distance, &command);
+            distance_with_aim = apply_command_with_aim(distance_with_aim, &command);
+        }
+
+        Ok(Day02 {
+            distance,
+            distance_with_aim,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        let (horizontal, depth) = self.distance;
+        Ok((horizontal * depth).to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        let (horizontal, depth, _) = self.distance_with_aim;
+        Ok((horizontal * depth).to_string())
+    }
+
+    // Both positions are tracked command by command as the input streams in
+    fn solved_in_parse(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static COMMANDS: [Command; 6] = [
+        Forward(5),
+        Down(5),
+        Forward(8),
+        Up(3),
+        Down(8),
+        Forward(2)
+    ];
+
+    #[test]
+    fn test_command_from_string() {
+        assert_eq!(Ok(Forward(1)), Command::try_from("forward 1"));
+        assert_eq!(Ok(Up(17)), Command::try_from("up 17"));
+        assert_eq!(Ok(Down(22)), Command::try_from("down 22"));
+        assert_eq!(
+            Err(ParseError::new(2, 1, 1, "\"forward\", \"down\" or \"up\"")),
+            Command::try_from("OH 7")
+        );
+
+        assert_eq!(
+            Err(ParseError::new(2, 1, 9, "a magnitude")),
+            Command::try_from("forward NO")
+        );
+
+        assert_eq!(
+            Err(ParseError::new(2, 1, 6, "a space followed by a magnitude")),
+            Command::try_from("OH_NO")
+        );
+    }
+
+    #[test]
+    fn test_get_distance() {
+        assert_eq!((15, 10), get_distance(&COMMANDS));
+    }
+
+    #[test]
+    fn test_get_distance_with_aim() {
+        assert_eq!((15, 60), get_distance_with_aim(&COMMANDS));
+    }
+}
\ No newline at end of file
@@ -1,28 +1,6 @@
-use std::fs::File;
-use std::io::BufRead;
-use std::{env, error, io};
-
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let file = File::open(path)?;
-
-        let values: Vec<String> = io::BufReader::new(file)
-            .lines()
-            .map(|line| line.unwrap())
-            .collect();
-
-        println!("Power consumption: {}", get_power_consumption(&values));
-        println!("Life support rating: {}", get_life_support_rating(&values));
-
-        Ok(())
-    } else {
-        Err("Usage: day03 INPUT_FILE_PATH".into())
-    }
-}
-
-fn get_power_consumption(values: &[String]) -> u32 {
+use crate::solution::{Input, Solution};
+use std::error;
+pub fn get_power_consumption(values: &[String]) -> u32 {
     let width = values[0].len();
     let gamma = get_gamma(values);
 
@@ -37,7 +15,7 @@ fn get_power_consumption(values: &[String]) -> u32 {
     gamma * epsilon
 }
 
-fn get_gamma(values: &[String]) -> u32 {
+pub fn get_gamma(values: &[String]) -> u32 {
     let mut ones = vec![0u32; values[0].len()];
 
     for value in values {
@@ -61,11 +39,11 @@ fn get_gamma(values: &[String]) -> u32 {
     })
 }
 
-fn get_life_support_rating(values: &[String]) -> u32 {
+pub fn get_life_support_rating(values: &[String]) -> u32 {
     get_oxygen_generator_rating(values) * get_co2_scrubber_rating(values)
 }
 
-fn get_oxygen_generator_rating(values: &[String]) -> u32 {
+pub fn get_oxygen_generator_rating(values: &[String]) -> u32 {
     get_life_support_value(values, 0, |ones_at_position, len| {
         if ones_at_position > len / 2 || (len % 2 == 0 && ones_at_position == len / 2) {
             '1'
@@ -75,7 +53,7 @@ fn get_oxygen_generator_rating(values: &[String]) -> u32 {
     })
 }
 
-fn get_co2_scrubber_rating(values: &[String]) -> u32 {
+pub fn get_co2_scrubber_rating(values: &[String]) -> u32 {
     get_life_support_value(values, 0, |ones_at_position, len| {
         if ones_at_position > len / 2 || (len % 2 == 0 && ones_at_position == len / 2) {
             '0'
@@ -85,7 +63,7 @@ fn get_co2_scrubber_rating(values: &[String]) -> u32 {
     })
 }
 
-fn get_life_support_value(
+pub fn get_life_support_value(
     values: &[String],
     position: usize,
     get_target_digit: impl Fn(usize, usize) -> char,
@@ -110,6 +88,26 @@ fn get_life_support_value(
     }
 }
 
+pub struct Day03 {
+    values: Vec<String>,
+}
+
+impl Solution for Day03 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day03 {
+            values: input.text()?.lines().map(String::from).collect(),
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(get_power_consumption(&self.values).to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(get_life_support_rating(&self.values).to_string())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
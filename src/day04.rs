@@ -1,56 +1,11 @@
+use crate::solution::{Input, Solution};
+use std::error;
 use self::Cell::{Marked, Unmarked};
-use std::fs::File;
-use std::io::BufRead;
 use std::str::FromStr;
-use std::{env, error, io};
 
-const BOARD_SIZE: usize = 5;
+pub const BOARD_SIZE: usize = 5;
 
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let file = File::open(path)?;
-
-        let mut lines = io::BufReader::new(file).lines();
-
-        let selections: Vec<u8> = lines
-            .next()
-            .unwrap()?
-            .split(',')
-            .filter_map(|n| n.parse().ok())
-            .collect();
-
-        let boards: Vec<BingoBoard> = {
-            let board_lines: Vec<String> = lines
-                .filter_map(|line| line.ok())
-                .filter(|line| !line.is_empty())
-                .collect();
-
-            board_lines
-                .chunks_exact(BOARD_SIZE)
-                .map(|chunk| {
-                    BingoBoard::try_from(TryInto::<&[String; BOARD_SIZE]>::try_into(chunk).unwrap())
-                        .unwrap()
-                })
-                .collect()
-        };
-
-        if let Some(score) = get_score_from_first_winner(boards.clone(), &selections) {
-            println!("Score from first winner: {}", score);
-        }
-
-        if let Some(score) = get_score_from_last_winner(boards, &selections) {
-            println!("Score from last winner: {}", score);
-        }
-
-        Ok(())
-    } else {
-        Err("Usage: day04 INPUT_FILE_PATH".into())
-    }
-}
-
-fn get_score_from_first_winner(mut boards: Vec<BingoBoard>, selections: &[u8]) -> Option<u32> {
+pub fn get_score_from_first_winner(mut boards: Vec<BingoBoard>, selections: &[u8]) -> Option<u32> {
     for selection in selections {
         for board in boards.iter_mut() {
             board.mark(*selection);
@@ -64,7 +19,7 @@ fn get_score_from_first_winner(mut boards: Vec<BingoBoard>, selections: &[u8]) -
     None
 }
 
-fn get_score_from_last_winner(mut boards: Vec<BingoBoard>, selections: &[u8]) -> Option<u32> {
+pub fn get_score_from_last_winner(mut boards: Vec<BingoBoard>, selections: &[u8]) -> Option<u32> {
     for selection in selections {
         for board in boards.iter_mut() {
             board.mark(*selection);
@@ -81,13 +36,13 @@ fn get_score_from_last_winner(mut boards: Vec<BingoBoard>, selections: &[u8]) ->
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum Cell {
+pub enum Cell {
     Unmarked(u8),
     Marked(u8),
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-struct BingoBoard {
+pub struct BingoBoard {
     cells: [[Cell; BOARD_SIZE]; BOARD_SIZE],
 }
 
@@ -151,7 +106,7 @@ impl TryFrom<&[String; BOARD_SIZE]> for BingoBoard {
                 .collect();
 
             if numbers.len() != BOARD_SIZE {
-                return Err("Bad row length".into());
+                return Err("Bad row length");
             }
 
             for col in 0..numbers.len() {
@@ -163,6 +118,52 @@ impl TryFrom<&[String; BOARD_SIZE]> for BingoBoard {
     }
 }
 
+pub struct Day04 {
+    selections: Vec<u8>,
+    boards: Vec<BingoBoard>,
+}
+
+impl Solution for Day04 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        let mut lines = input.text()?.lines();
+
+        let selections: Vec<u8> = lines
+            .next()
+            .ok_or("Missing selections")?
+            .split(',')
+            .filter_map(|n| n.parse().ok())
+            .collect();
+
+        let board_lines: Vec<String> = lines
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect();
+
+        let boards = board_lines
+            .chunks_exact(BOARD_SIZE)
+            .map(|chunk| {
+                BingoBoard::try_from(TryInto::<&[String; BOARD_SIZE]>::try_into(chunk).unwrap())
+            })
+            .collect::<Result<Vec<BingoBoard>, _>>()?;
+
+        Ok(Day04 { selections, boards })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        let score = get_score_from_first_winner(self.boards.clone(), &self.selections)
+            .ok_or("No winning board")?;
+
+        Ok(score.to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        let score = get_score_from_last_winner(self.boards.clone(), &self.selections)
+            .ok_or("No winning board")?;
+
+        Ok(score.to_string())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,75 +1,35 @@
+use crate::parse_error::{parse_lines, ParseError};
+use crate::solution::{Input, Solution};
+use std::error;
 use std::cmp::{max, min};
-use std::fs::File;
-use std::io::BufRead;
-use std::{env, error, io};
 use std::collections::HashMap;
 use std::str::FromStr;
 
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let segments: Vec<LineSegment> = io::BufReader::new(File::open(path)?)
-            .lines()
-            .map(|line| LineSegment::from_str(line.unwrap().as_str()).unwrap())
-            .collect();
-
-        {
-            let mut vent_map = VentMap::new();
-
-            segments.iter()
-                .filter(|segment| segment.is_horizontal() || segment.is_vertical())
-                .for_each(|segment| vent_map.add_line_segment(segment));
-
-            println!(
-                "Cells with multiple vents (horizontal/vertical only): {}",
-                vent_map.get_multi_vent_cell_count()
-            );
-        }
-
-        {
-            let mut vent_map = VentMap::new();
-
-            segments.iter()
-                .for_each(|segment| vent_map.add_line_segment(segment));
-
-            println!(
-                "Cells with multiple vents (all): {}",
-                vent_map.get_multi_vent_cell_count()
-            );
-        }
-
-        Ok(())
-    } else {
-        Err("Usage: day01 INPUT_FILE_PATH".into())
-    }
-}
-
 #[derive(Debug, Eq, Hash, PartialEq)]
-struct Point {
+pub struct Point {
     x: usize,
     y: usize,
 }
 
 impl FromStr for Point {
-    type Err = Box<dyn std::error::Error>;
+    type Err = ParseError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let pieces: Vec<&str> = value.split(',').collect();
-
-        if pieces.len() != 2 {
-            return Err("Points must have two comma-separated coordinates".into());
-        }
+        let (x, y) = value
+            .split_once(',')
+            .ok_or_else(|| ParseError::at_end(5, value, "a comma between coordinates"))?;
 
         Ok(Point {
-            x: pieces[0].parse()?,
-            y: pieces[1].parse()?,
+            x: x.parse()
+                .map_err(|_| ParseError::at(5, value, x, "an x coordinate"))?,
+            y: y.parse()
+                .map_err(|_| ParseError::at(5, value, y, "a y coordinate"))?,
         })
     }
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct LineSegment {
+pub struct LineSegment {
     start: Point,
     end: Point,
 }
@@ -111,27 +71,31 @@ impl LineSegment {
 }
 
 impl FromStr for LineSegment {
-    type Err = Box<dyn std::error::Error>;
+    type Err = ParseError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let pieces: Vec<&str> = value.split(" -> ").collect();
-
-        if pieces.len() != 2 {
-            return Err("Line segments must have two vectors separated by \" -> \"".into());
-        }
+        let (start, end) = value
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::at_end(5, value, "\" -> \" between points"))?;
 
         Ok(LineSegment {
-            start: Point::from_str(pieces[0])?,
-            end: Point::from_str(pieces[1])?,
+            start: Point::from_str(start).map_err(|error| error.within(value, start))?,
+            end: Point::from_str(end).map_err(|error| error.within(value, end))?,
         })
     }
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct VentMap {
+pub struct VentMap {
     cells: HashMap<Point, u32>,
 }
 
+impl Default for VentMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl VentMap {
     pub fn new() -> Self {
         VentMap { cells: HashMap::new() }
@@ -152,6 +116,39 @@ impl VentMap {
     }
 }
 
+pub struct Day05 {
+    segments: Vec<LineSegment>,
+}
+
+impl Solution for Day05 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day05 {
+            segments: parse_lines(input.text()?, LineSegment::from_str)?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        let mut vent_map = VentMap::new();
+
+        self.segments
+            .iter()
+            .filter(|segment| segment.is_horizontal() || segment.is_vertical())
+            .for_each(|segment| vent_map.add_line_segment(segment));
+
+        Ok(vent_map.get_multi_vent_cell_count().to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        let mut vent_map = VentMap::new();
+
+        self.segments
+            .iter()
+            .for_each(|segment| vent_map.add_line_segment(segment));
+
+        Ok(vent_map.get_multi_vent_cell_count().to_string())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -170,6 +167,16 @@ mod test {
             },
             LineSegment::from_str("1,2 -> 87,22").unwrap()
         );
+
+        assert_eq!(
+            Err(ParseError::new(5, 1, 11, "a y coordinate")),
+            LineSegment::from_str("1,2 -> 87,x")
+        );
+
+        assert_eq!(
+            Err(ParseError::new(5, 1, 4, "\" -> \" between points")),
+            LineSegment::from_str("1,2")
+        );
     }
 
     #[test]
@@ -199,7 +206,7 @@ mod test {
             "5,5 -> 8,2",
         ]
         .iter()
-        .map(|line| LineSegment::from_str(*line).unwrap())
+        .map(|line| LineSegment::from_str(line).unwrap())
         .collect();
 
         {
@@ -1,30 +1,7 @@
-use std::fs::File;
-use std::io::BufRead;
+use crate::solution::{Input, Solution};
+use std::error;
 use std::str::FromStr;
-use std::{env, error, io};
-
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let respawn_times: Vec<u8> = io::BufReader::new(File::open(path)?)
-            .split(b',')
-            .map(|chunk| String::from_utf8(chunk.unwrap()).unwrap())
-            .map(|respawn_time| u8::from_str(respawn_time.as_str()).unwrap())
-            .collect();
-
-        let school = SchoolOfFish::new(&respawn_times);
-
-        println!("Fish after 80 days: {}", school.get_population(80));
-        println!("Fish after 256 days: {}", school.get_population(256));
-
-        Ok(())
-    } else {
-        Err("Usage: day06 INPUT_FILE_PATH".into())
-    }
-}
-
-struct SchoolOfFish {
+pub struct SchoolOfFish {
     fish_by_initial_respawn_time: [u64; 9],
 }
 
@@ -51,6 +28,33 @@ impl SchoolOfFish {
     }
 }
 
+pub struct Day06 {
+    school: SchoolOfFish,
+}
+
+impl Solution for Day06 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        let respawn_times = input
+            .text()?
+            .trim()
+            .split(',')
+            .map(u8::from_str)
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        Ok(Day06 {
+            school: SchoolOfFish::new(&respawn_times),
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.school.get_population(80).to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.school.get_population(256).to_string())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,43 +1,9 @@
-use std::fs::File;
-use std::io::BufRead;
+use crate::solution::{Input, Solution};
+use std::error;
 use std::str::FromStr;
-use std::{env, error, io};
+pub type CostFunction = dyn Fn(u32) -> u32;
 
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let positions: Vec<u32> = io::BufReader::new(File::open(path)?)
-            .split(b',')
-            .map(|chunk| String::from_utf8(chunk.unwrap()).unwrap())
-            .map(|position| u32::from_str(position.as_str()).unwrap())
-            .collect();
-
-        let flotilla = CrabFlotilla::new(positions);
-
-        let linear_target = flotilla.optimal_alignment_target(&CrabFlotilla::linear_cost);
-
-        println!(
-            "Optimal alignment target with linear movement cost: {} ({} fuel)",
-            linear_target.0, linear_target.1
-        );
-
-        let geometric_target = flotilla.optimal_alignment_target(&CrabFlotilla::geometric_cost);
-
-        println!(
-            "Optimal alignment target with geometric movement cost: {} ({} fuel)",
-            geometric_target.0, geometric_target.1
-        );
-
-        Ok(())
-    } else {
-        Err("Usage: day07 INPUT_FILE_PATH".into())
-    }
-}
-
-type CostFunction = dyn Fn(u32) -> u32;
-
-struct CrabFlotilla {
+pub struct CrabFlotilla {
     initial_positions: Vec<u32>,
 }
 
@@ -68,11 +34,7 @@ impl CrabFlotilla {
             .iter()
             .map(|&position| {
                 // Nightly has u32::abs_diff, which would make this much, much cleaner
-                let abs_diff = if position > target {
-                    position - target
-                } else {
-                    target - position
-                };
+                let abs_diff = position.abs_diff(target);
 
                 cost_function(abs_diff)
             })
@@ -88,6 +50,41 @@ impl CrabFlotilla {
     }
 }
 
+pub struct Day07 {
+    flotilla: CrabFlotilla,
+}
+
+impl Solution for Day07 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        let positions = input
+            .text()?
+            .trim()
+            .split(',')
+            .map(u32::from_str)
+            .collect::<Result<Vec<u32>, _>>()?;
+
+        Ok(Day07 {
+            flotilla: CrabFlotilla::new(positions),
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        let (_, fuel) = self
+            .flotilla
+            .optimal_alignment_target(&CrabFlotilla::linear_cost);
+
+        Ok(fuel.to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        let (_, fuel) = self
+            .flotilla
+            .optimal_alignment_target(&CrabFlotilla::geometric_cost);
+
+        Ok(fuel.to_string())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,7 +1,6 @@
-use std::{env, error, io};
+use crate::solution::{Input, Solution};
+use std::error;
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::BufRead;
 use std::str::FromStr;
 
 /*
@@ -36,39 +35,22 @@ b    .  b    .  .    c  b    c  b    c
  9     | 6             | A B C D _ F G
  */
 
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let entries: Vec<NotesEntry> = io::BufReader::new(File::open(path)?).lines()
-            .map(|line| line.unwrap())
-            .map(|line| NotesEntry::from_str(line.as_str()).unwrap())
-            .collect();
-
-        println!("Unambiguous output digit count: {}", count_unambiguous_output_digits(&entries));
-
-        let decoded_sum: u32 = entries.iter()
-            .map(|entry| SevenDigitDisplay::new(&entry.scrambled_digits).decode(&entry.output_digits))
-            .sum();
-
-        println!("Sum of decoded numbers: {}", decoded_sum);
-
-        Ok(())
-    } else {
-        Err("Usage: day08 INPUT_FILE_PATH".into())
-    }
+pub fn count_unambiguous_output_digits(entries: &[NotesEntry]) -> usize {
+    entries.iter()
+        .map(|entry| entry.unambiguous_output_digit_count())
+        .sum()
 }
 
-fn count_unambiguous_output_digits(entries: &[NotesEntry]) -> usize {
+pub fn decoded_output_sum(entries: &[NotesEntry]) -> u32 {
     entries.iter()
-        .map(|entry| entry.unambiguous_output_digit_count())
+        .map(|entry| SevenDigitDisplay::new(&entry.scrambled_digits).decode(&entry.output_digits))
         .sum()
 }
 
-type SegmentGroup = Vec<char>;
+pub type SegmentGroup = Vec<char>;
 
 #[derive(Debug, Eq, PartialEq)]
-struct NotesEntry {
+pub struct NotesEntry {
     scrambled_digits: [SegmentGroup; 10],
     output_digits: [SegmentGroup; 4],
 }
@@ -113,7 +95,7 @@ impl FromStr for NotesEntry {
     }
 }
 
-struct SevenDigitDisplay {
+pub struct SevenDigitDisplay {
     positions_by_character: HashMap<char, u8>,
 }
 
@@ -276,6 +258,30 @@ impl SevenDigitDisplay {
     }
 }
 
+pub struct Day08 {
+    entries: Vec<NotesEntry>,
+}
+
+impl Solution for Day08 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day08 {
+            entries: input
+                .text()?
+                .lines()
+                .map(NotesEntry::from_str)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(count_unambiguous_output_digits(&self.entries).to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(decoded_output_sum(&self.entries).to_string())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
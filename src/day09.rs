@@ -0,0 +1,169 @@
+use crate::grid::{Grid, Neighborhood, RowCol};
+use crate::parse_error::ParseError;
+use crate::solution::{Input, Solution};
+use std::collections::{HashSet, VecDeque};
+use std::str::FromStr;
+use std::error;
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct HeightMap {
+    heights: Grid<u8>,
+}
+
+impl HeightMap {
+    fn find_local_minima(&self) -> Vec<RowCol> {
+        self.heights
+            .enumerate()
+            .filter(|&(position, height)| {
+                !self
+                    .heights
+                    .neighbors(position, Neighborhood::VON_NEUMANN)
+                    .any(|neighbor| self.heights[neighbor] <= *height)
+            })
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    pub fn combined_risk_level_at_local_minima(&self) -> u32 {
+        self.find_local_minima()
+            .iter()
+            .map(|&position| (self.heights[position] + 1) as u32)
+            .sum()
+    }
+
+    fn basin_members(&self, origin: RowCol) -> Vec<RowCol> {
+        if self.heights[origin] == 9 {
+            return vec![];
+        }
+
+        let mut explored = HashSet::new();
+        let mut queue = VecDeque::new();
+        let mut basin_members = Vec::new();
+
+        queue.push_front(origin);
+
+        while let Some(position) = queue.pop_front() {
+            if explored.insert(position) && self.heights[position] < 9 {
+                basin_members.push(position);
+
+                queue.extend(
+                    self.heights
+                        .neighbors(position, Neighborhood::VON_NEUMANN)
+                        .filter(|neighbor| !explored.contains(neighbor)),
+                );
+            }
+        }
+
+        basin_members
+    }
+
+    pub fn largest_basin_size_product(&self, n: usize) -> u32 {
+        let mut basin_sizes: Vec<u32> = self
+            .find_local_minima()
+            .iter()
+            .map(|&position| self.basin_members(position).len() as u32)
+            .collect();
+
+        basin_sizes.sort_by(|a, b| b.cmp(a));
+
+        basin_sizes.iter().take(n).product()
+    }
+}
+
+impl FromStr for HeightMap {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let heights = Grid::parse(9, s, "a height digit", |c| {
+            c.to_digit(10).map(|height| height as u8)
+        })?;
+
+        Ok(HeightMap { heights })
+    }
+}
+
+pub struct Day09 {
+    height_map: HeightMap,
+}
+
+impl Solution for Day09 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day09 {
+            height_map: HeightMap::from_str(input.text()?)?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.height_map.combined_risk_level_at_local_minima().to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.height_map.largest_basin_size_product(3).to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    static EXAMPLE_MAP_STRING: &str = indoc! {"
+            2199943210
+            3987894921
+            9856789892
+            8767896789
+            9899965678
+        "};
+
+    #[test]
+    fn test_height_map_from_string() {
+        let expected = HeightMap {
+            heights: Grid::from_rows([
+                [2, 1, 9, 9, 9, 4, 3, 2, 1, 0],
+                [3, 9, 8, 7, 8, 9, 4, 9, 2, 1],
+                [9, 8, 5, 6, 7, 8, 9, 8, 9, 2],
+                [8, 7, 6, 7, 8, 9, 6, 7, 8, 9],
+                [9, 8, 9, 9, 9, 6, 5, 6, 7, 8],
+            ]),
+        };
+
+        assert_eq!(expected, HeightMap::from_str(EXAMPLE_MAP_STRING).unwrap());
+    }
+
+    #[test]
+    fn test_find_local_minima() {
+        assert_eq!(
+            vec![(0, 1), (0, 9), (2, 2), (4, 6)],
+            HeightMap::from_str(EXAMPLE_MAP_STRING)
+                .unwrap()
+                .find_local_minima()
+        );
+    }
+
+    #[test]
+    fn test_combined_risk_level_at_local_minima() {
+        assert_eq!(
+            15,
+            HeightMap::from_str(EXAMPLE_MAP_STRING)
+                .unwrap()
+                .combined_risk_level_at_local_minima()
+        );
+    }
+
+    #[test]
+    fn test_find_basin_members() {
+        let height_map = HeightMap::from_str(EXAMPLE_MAP_STRING).unwrap();
+
+        assert_eq!(3, height_map.basin_members((0, 1)).len());
+        assert_eq!(9, height_map.basin_members((0, 9)).len());
+        assert_eq!(14, height_map.basin_members((2, 2)).len());
+        assert_eq!(9, height_map.basin_members((4, 6)).len());
+    }
+
+    #[test]
+    fn test_largest_basin_size_product() {
+        let height_map = HeightMap::from_str(EXAMPLE_MAP_STRING).unwrap();
+
+        assert_eq!(1134, height_map.largest_basin_size_product(3));
+    }
+}
@@ -1,43 +1,15 @@
-use crate::NavigationSyntaxError::{Incomplete, Invalid};
+use crate::solution::{Input, Solution};
+use std::error;
+use self::NavigationSyntaxError::{Incomplete, Invalid};
 use std::collections::VecDeque;
-use std::fs::File;
-use std::io::BufRead;
-use std::{env, error, io};
-
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        println!(
-            "Total syntax score: {}",
-            io::BufReader::new(File::open(path)?)
-                .lines()
-                .map(|line| syntax_score(line.unwrap().as_str()))
-                .sum::<u32>()
-        );
-
-        println!(
-            "Median autocomplete score: {}",
-            median_autocomplete_score(
-                io::BufReader::new(File::open(path)?)
-                    .lines()
-                    .map(|line| line.unwrap())
-            )
-        );
-
-        Ok(())
-    } else {
-        Err("Usage: day10 INPUT_FILE_PATH".into())
-    }
-}
 
 #[derive(Debug, Eq, PartialEq)]
-enum NavigationSyntaxError {
+pub enum NavigationSyntaxError {
     Incomplete(String),
     Invalid(char),
 }
 
-fn analyze_line(line: &str) -> Result<(), NavigationSyntaxError> {
+pub fn analyze_line(line: &str) -> Result<(), NavigationSyntaxError> {
     let mut stack = VecDeque::new();
 
     for c in line.chars() {
@@ -69,7 +41,7 @@ fn analyze_line(line: &str) -> Result<(), NavigationSyntaxError> {
     }
 }
 
-fn expected_closer(opener: &char) -> char {
+pub fn expected_closer(opener: &char) -> char {
     match opener {
         '(' => ')',
         '[' => ']',
@@ -79,7 +51,7 @@ fn expected_closer(opener: &char) -> char {
     }
 }
 
-fn expected_opener(closer: &char) -> char {
+pub fn expected_opener(closer: &char) -> char {
     match closer {
         ')' => '(',
         ']' => '[',
@@ -89,7 +61,7 @@ fn expected_opener(closer: &char) -> char {
     }
 }
 
-fn syntax_score(line: &str) -> u32 {
+pub fn syntax_score(line: &str) -> u32 {
     if let Err(Invalid(c)) = analyze_line(line) {
         match c {
             ')' => 3,
@@ -103,7 +75,7 @@ fn syntax_score(line: &str) -> u32 {
     }
 }
 
-fn autocomplete_score(line: &str) -> u64 {
+pub fn autocomplete_score(line: &str) -> u64 {
     if let Err(Incomplete(completion)) = analyze_line(line) {
         completion
             .chars()
@@ -120,21 +92,65 @@ fn autocomplete_score(line: &str) -> u64 {
     }
 }
 
-fn median_autocomplete_score(lines: impl Iterator<Item = String>) -> u64 {
-    let mut scores: Vec<u64> = lines
+pub fn median_autocomplete_score(lines: impl Iterator<Item = String>) -> u64 {
+    let scores: Vec<u64> = lines
         .map(|line| autocomplete_score(line.as_str()))
         .filter(|&score| score > 0)
         .collect();
 
+    median(scores).unwrap()
+}
+
+fn median(mut scores: Vec<u64>) -> Option<u64> {
     scores.sort_unstable();
+    scores.get(scores.len() / 2).copied()
+}
 
-    scores[scores.len() / 2]
+pub struct Day10 {
+    syntax_score: u32,
+    autocomplete_scores: Vec<u64>,
+}
+
+impl Solution for Day10 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        let mut day = Day10 {
+            syntax_score: 0,
+            autocomplete_scores: Vec::new(),
+        };
+
+        for line in input.lines(0)? {
+            let line = line?;
+
+            day.syntax_score += syntax_score(&line);
+
+            match autocomplete_score(&line) {
+                0 => {}
+                score => day.autocomplete_scores.push(score),
+            }
+        }
+
+        Ok(day)
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.syntax_score.to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        let score = median(self.autocomplete_scores.clone()).ok_or("No incomplete lines")?;
+        Ok(score.to_string())
+    }
+
+    // Lines are scored one at a time as they stream in; part 2 only has to find the median
+    fn solved_in_parse(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::NavigationSyntaxError::Invalid;
+    use self::NavigationSyntaxError::Invalid;
     use indoc::indoc;
 
     const TEST_INPUT: &str = indoc! {"
@@ -196,7 +212,7 @@ mod test {
             26397,
             TEST_INPUT
                 .lines()
-                .map(|line| syntax_score(line))
+                .map(syntax_score)
                 .sum::<u32>()
         );
     }
@@ -214,7 +230,7 @@ mod test {
     fn test_median_autocomplete_score() {
         assert_eq!(
             288957,
-            median_autocomplete_score(TEST_INPUT.lines().map(|line| String::from(line)))
+            median_autocomplete_score(TEST_INPUT.lines().map(String::from))
         );
     }
 }
@@ -1,32 +1,13 @@
+use crate::grid::{Grid, Neighborhood};
+use crate::parse_error::ParseError;
+use crate::solution::{Input, Solution};
 use std::collections::HashSet;
 use std::str::FromStr;
-use std::{env, error};
+use std::error;
 
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        println!(
-            "Flashes after 100 steps: {}",
-            OctopusGrid::from_str(std::fs::read_to_string(path)?.as_str())?
-                .flashes_after_steps(100)
-        );
-
-        println!(
-            "Steps until synchronization: {}",
-            OctopusGrid::from_str(std::fs::read_to_string(path)?.as_str())?
-                .steps_until_synchronization()
-        );
-
-        Ok(())
-    } else {
-        Err("Usage: day11 INPUT_FILE_PATH".into())
-    }
-}
-
-#[derive(Debug, Eq, PartialEq)]
-struct OctopusGrid {
-    energy_levels: [[u8; 10]; 10],
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OctopusGrid {
+    energy_levels: Grid<u8>,
 }
 
 impl OctopusGrid {
@@ -41,38 +22,35 @@ impl OctopusGrid {
     }
 
     pub fn steps_until_synchronization(mut self) -> u32 {
+        let octopuses = (self.energy_levels.width() * self.energy_levels.height()) as u32;
         let mut step = 0;
 
         loop {
             step += 1;
 
-            if self.advance() == 100 {
+            if self.advance() == octopuses {
                 break step;
             }
         }
     }
 
     fn advance(&mut self) -> u32 {
-        for row in 0..10 {
-            for col in 0..10 {
-                self.energy_levels[row][col] += 1;
-            }
-        }
+        self.energy_levels
+            .iter_mut()
+            .for_each(|energy_level| *energy_level += 1);
 
         let mut flashed = HashSet::new();
 
         loop {
             let mut converged = true;
 
-            for row in 0..10 {
-                for col in 0..10 {
-                    if self.energy_levels[row][col] > 9 && flashed.insert((row, col)) {
-                        // This octopus just crossed the energy threshold and hasn't already flashed
-                        converged = false;
+            for position in self.energy_levels.positions() {
+                if self.energy_levels[position] > 9 && flashed.insert(position) {
+                    // This octopus just crossed the energy threshold and hasn't already flashed
+                    converged = false;
 
-                        for (neighbor_row, neighbor_col) in Self::neighbors(row, col) {
-                            self.energy_levels[neighbor_row][neighbor_col] += 1;
-                        }
+                    for neighbor in self.energy_levels.neighbors(position, Neighborhood::MOORE) {
+                        self.energy_levels[neighbor] += 1;
                     }
                 }
             }
@@ -82,55 +60,44 @@ impl OctopusGrid {
             }
         }
 
-        for row in 0..10 {
-            for col in 0..10 {
-                if self.energy_levels[row][col] > 9 {
-                    self.energy_levels[row][col] = 0;
-                }
-            }
-        }
+        self.energy_levels
+            .iter_mut()
+            .filter(|energy_level| **energy_level > 9)
+            .for_each(|energy_level| *energy_level = 0);
 
         flashed.len() as u32
     }
+}
 
-    fn neighbors(row: usize, col: usize) -> Vec<(usize, usize)> {
-        let mut neighbors = Vec::new();
-
-        let min_row = if row > 0 { row - 1 } else { row };
-        let max_row = if row < 9 { row + 1 } else { row };
-        let min_col = if col > 0 { col - 1 } else { col };
-        let max_col = if col < 9 { col + 1 } else { col };
+impl FromStr for OctopusGrid {
+    type Err = ParseError;
 
-        for neighbor_row in min_row..=max_row {
-            for neighbor_col in min_col..=max_col {
-                if neighbor_row != row || neighbor_col != col {
-                    neighbors.push((neighbor_row, neighbor_col));
-                }
-            }
-        }
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let energy_levels = Grid::parse(11, string, "an energy level digit", |c| {
+            c.to_digit(10).map(|energy_level| energy_level as u8)
+        })?;
 
-        neighbors
+        Ok(OctopusGrid { energy_levels })
     }
 }
 
-impl FromStr for OctopusGrid {
-    type Err = Box<dyn error::Error>;
+pub struct Day11 {
+    grid: OctopusGrid,
+}
 
-    fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let energy_levels: [[u8; 10]; 10] = string
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| c as u8 - b'0')
-                    .collect::<Vec<u8>>()
-                    .try_into()
-                    .unwrap()
-            })
-            .collect::<Vec<[u8; 10]>>()
-            .try_into()
-            .unwrap();
+impl Solution for Day11 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day11 {
+            grid: OctopusGrid::from_str(input.text()?)?,
+        })
+    }
 
-        Ok(OctopusGrid { energy_levels })
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.grid.clone().flashes_after_steps(100).to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.grid.clone().steps_until_synchronization().to_string())
     }
 }
 
@@ -155,7 +122,7 @@ mod test {
     #[test]
     fn test_octopus_grid_from_string() {
         let expected = OctopusGrid {
-            energy_levels: [
+            energy_levels: Grid::from_rows([
                 [5, 4, 8, 3, 1, 4, 3, 2, 2, 3],
                 [2, 7, 4, 5, 8, 5, 4, 7, 1, 1],
                 [5, 2, 6, 4, 5, 5, 6, 1, 7, 3],
@@ -166,7 +133,7 @@ mod test {
                 [6, 8, 8, 2, 8, 8, 1, 1, 3, 4],
                 [4, 8, 4, 6, 8, 4, 8, 5, 5, 4],
                 [5, 2, 8, 3, 7, 5, 1, 5, 2, 6],
-            ],
+            ]),
         };
 
         assert_eq!(expected, OctopusGrid::from_str(TEST_GRID_STRING).unwrap());
@@ -179,7 +146,7 @@ mod test {
         assert_eq!(0, grid.advance());
         assert_eq!(
             OctopusGrid {
-                energy_levels: [
+                energy_levels: Grid::from_rows([
                     [6, 5, 9, 4, 2, 5, 4, 3, 3, 4],
                     [3, 8, 5, 6, 9, 6, 5, 8, 2, 2],
                     [6, 3, 7, 5, 6, 6, 7, 2, 8, 4],
@@ -190,7 +157,7 @@ mod test {
                     [7, 9, 9, 3, 9, 9, 2, 2, 4, 5],
                     [5, 9, 5, 7, 9, 5, 9, 6, 6, 5],
                     [6, 3, 9, 4, 8, 6, 2, 6, 3, 7],
-                ]
+                ])
             },
             grid
         );
@@ -198,7 +165,7 @@ mod test {
         assert_eq!(35, grid.advance());
         assert_eq!(
             OctopusGrid {
-                energy_levels: [
+                energy_levels: Grid::from_rows([
                     [8, 8, 0, 7, 4, 7, 6, 5, 5, 5],
                     [5, 0, 8, 9, 0, 8, 7, 0, 5, 4],
                     [8, 5, 9, 7, 8, 8, 9, 6, 0, 8],
@@ -209,7 +176,7 @@ mod test {
                     [0, 0, 0, 0, 0, 0, 7, 4, 5, 6],
                     [9, 0, 0, 0, 0, 0, 0, 8, 7, 6],
                     [8, 7, 0, 0, 0, 0, 6, 8, 4, 8],
-                ]
+                ])
             },
             grid
         );
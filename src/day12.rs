@@ -1,32 +1,11 @@
-use self::ExplorationQueueEntry::*;
+use crate::search::{self, Backtrack};
+use crate::solution::{Input, Solution};
 use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use std::{env, error};
-
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let cave_graph = CaveGraph::from_str(std::fs::read_to_string(path)?.as_str())?;
-
-        println!(
-            "Distinct paths through caves (revisiting small caves not allowed): {}",
-            cave_graph.find_paths(false).len()
-        );
-
-        println!(
-            "Distinct paths through caves (revisiting small caves allowed): {}",
-            cave_graph.find_paths(true).len()
-        );
-
-        Ok(())
-    } else {
-        Err("Usage: day12 INPUT_FILE_PATH".into())
-    }
-}
+use std::error;
 
 #[derive(Debug, Eq, PartialEq)]
-struct CaveGraph {
+pub struct CaveGraph {
     connections: HashMap<String, HashSet<String>>,
 }
 
@@ -34,39 +13,15 @@ impl CaveGraph {
     pub fn find_paths(&self, allow_small_cave_revisit: bool) -> HashSet<Vec<&str>> {
         let mut paths = HashSet::new();
 
-        let mut current_path = Vec::new();
-        let mut exploration_stack = vec![Cave("start")];
-
-        while let Some(entry) = exploration_stack.pop() {
-            match entry {
-                Cave(cave) => {
-                    current_path.push(cave);
-
-                    if cave == "end" {
-                        paths.insert(current_path.clone());
-                    } else {
-                        self.connections
-                            .get(cave)
-                            .unwrap()
-                            .iter()
-                            .filter(|&connection| {
-                                Self::allow_visit(
-                                    connection,
-                                    &current_path,
-                                    allow_small_cave_revisit,
-                                )
-                            })
-                            .for_each(|connection| {
-                                exploration_stack.push(Backtrack);
-                                exploration_stack.push(Cave(connection.as_str()));
-                            });
-                    }
-                }
-                Backtrack => {
-                    current_path.pop();
-                }
-            }
-        }
+        let mut walk = CaveWalk {
+            cave_graph: self,
+            caves: vec!["start"],
+            allow_small_cave_revisit,
+        };
+
+        search::depth_first_goals(&mut walk, |walk| {
+            paths.insert(walk.caves.clone());
+        });
 
         paths
     }
@@ -132,10 +87,64 @@ impl FromStr for CaveGraph {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
-enum ExplorationQueueEntry<'a> {
-    Cave(&'a str),
-    Backtrack,
+// A single path through a cave graph that grows and shrinks as the search goes forward and back.
+// Paths can be long and numerous, so this spares each partial path its own copy of the caves that
+// led to it.
+struct CaveWalk<'a> {
+    cave_graph: &'a CaveGraph,
+    caves: Vec<&'a str>,
+    allow_small_cave_revisit: bool,
+}
+
+impl<'a> Backtrack for CaveWalk<'a> {
+    type Step = &'a str;
+
+    fn steps(&self) -> Vec<&'a str> {
+        let cave = self.caves.last().unwrap();
+
+        self.cave_graph
+            .connections
+            .get(*cave)
+            .unwrap()
+            .iter()
+            .filter(|&connection| {
+                CaveGraph::allow_visit(connection, &self.caves, self.allow_small_cave_revisit)
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn take(&mut self, cave: &'a str) {
+        self.caves.push(cave);
+    }
+
+    fn undo(&mut self, _: &'a str) {
+        self.caves.pop();
+    }
+
+    fn is_goal(&self) -> bool {
+        self.caves.last() == Some(&"end")
+    }
+}
+
+pub struct Day12 {
+    cave_graph: CaveGraph,
+}
+
+impl Solution for Day12 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day12 {
+            cave_graph: CaveGraph::from_str(input.text()?)?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.cave_graph.find_paths(false).len().to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.cave_graph.find_paths(true).len().to_string())
+    }
 }
 
 #[cfg(test)]
@@ -385,21 +394,21 @@ mod test {
 
     #[test]
     fn test_allow_visit() {
-        assert!(!CaveGraph::allow_visit("start", &vec!["start", "a", "b"], false));
-        assert!(!CaveGraph::allow_visit("start", &vec!["start", "a", "b"], true));
+        assert!(!CaveGraph::allow_visit("start", &["start", "a", "b"], false));
+        assert!(!CaveGraph::allow_visit("start", &["start", "a", "b"], true));
 
-        assert!(CaveGraph::allow_visit("c", &vec!["start", "a", "b"], false));
-        assert!(CaveGraph::allow_visit("c", &vec!["start", "a", "b"], true));
+        assert!(CaveGraph::allow_visit("c", &["start", "a", "b"], false));
+        assert!(CaveGraph::allow_visit("c", &["start", "a", "b"], true));
 
-        assert!(!CaveGraph::allow_visit("a", &vec!["start", "a", "b"], false));
-        assert!(CaveGraph::allow_visit("a", &vec!["start", "a", "b"], true));
+        assert!(!CaveGraph::allow_visit("a", &["start", "a", "b"], false));
+        assert!(CaveGraph::allow_visit("a", &["start", "a", "b"], true));
 
-        assert!(!CaveGraph::allow_visit("a", &vec!["start", "a", "b", "a"], false));
-        assert!(!CaveGraph::allow_visit("a", &vec!["start", "a", "b", "a"], true));
-        assert!(!CaveGraph::allow_visit("b", &vec!["start", "a", "b", "a"], false));
-        assert!(!CaveGraph::allow_visit("b", &vec!["start", "a", "b", "a"], true));
+        assert!(!CaveGraph::allow_visit("a", &["start", "a", "b", "a"], false));
+        assert!(!CaveGraph::allow_visit("a", &["start", "a", "b", "a"], true));
+        assert!(!CaveGraph::allow_visit("b", &["start", "a", "b", "a"], false));
+        assert!(!CaveGraph::allow_visit("b", &["start", "a", "b", "a"], true));
 
-        assert!(CaveGraph::allow_visit("A", &vec!["start", "A", "a", "A", "b", "a"], false));
-        assert!(CaveGraph::allow_visit("A", &vec!["start", "A", "a", "A", "b", "a"], true));
+        assert!(CaveGraph::allow_visit("A", &["start", "A", "a", "A", "b", "a"], false));
+        assert!(CaveGraph::allow_visit("A", &["start", "A", "a", "A", "b", "a"], true));
     }
 }
@@ -1,75 +1,55 @@
-use crate::Fold::{Horizontal, Vertical};
+use crate::parse_error::ParseError;
+use crate::solution::{Input, Solution};
+use self::Fold::{Horizontal, Vertical};
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
-use std::fs::File;
-use std::io::BufRead;
 use std::str::FromStr;
-use std::{env, error, io};
+use std::error;
 
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
+pub fn load_page_and_folds(
+    lines: impl Iterator<Item = String>,
+) -> Result<(TransparentPage, Vec<Fold>), ParseError> {
+    let mut points = HashSet::new();
+    let mut folds = Vec::new();
 
-    if let Some(path) = args.get(1) {
-        let (page, folds) = load_page_and_folds(
-            io::BufReader::new(File::open(path)?)
-                .lines()
-                .filter_map(|line| line.ok()),
-        );
-
-        let page = page.apply_folds(&folds[0..1]);
-
-        println!("Dots visible after first fold: {}", page.distinct_points());
-
-        println!(
-            "Page after applying all folds:\n{}",
-            page.apply_folds(&folds[1..])
-        );
+    for (i, line) in lines.enumerate() {
+        if line.is_empty() {
+            continue;
+        }
 
-        Ok(())
-    } else {
-        Err("Usage: day13 INPUT_FILE_PATH".into())
+        if line.starts_with("fold along") {
+            folds.push(Fold::from_str(&line).map_err(|error| error.on_line(i + 1))?);
+        } else {
+            points.insert(Point::from_str(&line).map_err(|error| error.on_line(i + 1))?);
+        }
     }
-}
-
-fn load_page_and_folds(lines: impl Iterator<Item = String>) -> (TransparentPage, Vec<Fold>) {
-    let (point_lines, fold_lines): (Vec<String>, Vec<String>) = lines
-        .filter(|line| !line.is_empty())
-        .partition(|line| !line.starts_with("fold along"));
-
-    let points: HashSet<Point> = point_lines
-        .iter()
-        .map(|line| Point::from_str(line).unwrap())
-        .collect();
-
-    let folds: Vec<Fold> = fold_lines
-        .iter()
-        .map(|line| Fold::from_str(line).unwrap())
-        .collect();
 
-    (TransparentPage { points }, folds)
+    Ok((TransparentPage { points }, folds))
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Point {
+pub struct Point {
     x: u32,
     y: u32,
 }
 
 impl FromStr for Point {
-    type Err = Box<dyn error::Error>;
+    type Err = ParseError;
 
     fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let mut pieces = line.split(',');
+        let (x, y) = line
+            .split_once(',')
+            .ok_or_else(|| ParseError::at_end(13, line, "a comma between coordinates"))?;
 
         Ok(Point {
-            x: u32::from_str(pieces.next().unwrap())?,
-            y: u32::from_str(pieces.next().unwrap())?,
+            x: u32::from_str(x).map_err(|_| ParseError::at(13, line, x, "an x coordinate"))?,
+            y: u32::from_str(y).map_err(|_| ParseError::at(13, line, y, "a y coordinate"))?,
         })
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
-struct TransparentPage {
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransparentPage {
     points: HashSet<Point>,
 }
 
@@ -137,24 +117,60 @@ impl Display for TransparentPage {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-enum Fold {
+pub enum Fold {
     Horizontal(u32),
     Vertical(u32),
 }
 
 impl FromStr for Fold {
-    type Err = Box<dyn error::Error>;
+    type Err = ParseError;
 
     fn from_str(line: &str) -> Result<Self, Self::Err> {
         const PREFIX_LENGTH: usize = "fold along x=".len();
 
-        if line.starts_with("fold along x=") {
-            Ok(Vertical(u32::from_str(&line[PREFIX_LENGTH..])?))
+        let fold: fn(u32) -> Fold = if line.starts_with("fold along x=") {
+            Vertical
         } else if line.starts_with("fold along y=") {
-            Ok(Horizontal(u32::from_str(&line[PREFIX_LENGTH..])?))
+            Horizontal
         } else {
-            Err(format!("Could not parse line as fold: {}", line).into())
+            return Err(ParseError::at(13, line, line, "\"fold along x=\" or \"fold along y=\""));
+        };
+
+        let position = line.get(PREFIX_LENGTH..).unwrap_or_default();
+
+        u32::from_str(position)
+            .map(fold)
+            .map_err(|_| ParseError::at(13, line, position, "a fold position"))
+    }
+}
+
+pub struct Day13 {
+    page: TransparentPage,
+    folds: Vec<Fold>,
+}
+
+impl Solution for Day13 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        let (page, folds) = load_page_and_folds(input.text()?.lines().map(String::from))?;
+
+        if folds.is_empty() {
+            return Err("No folds".into());
         }
+
+        Ok(Day13 { page, folds })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self
+            .page
+            .clone()
+            .apply_folds(&self.folds[0..1])
+            .distinct_points()
+            .to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.page.clone().apply_folds(&self.folds).to_string())
     }
 }
 
@@ -221,8 +237,22 @@ mod test {
             load_page_and_folds(
                 TEST_PAGE_AND_FOLDS_STRING
                     .lines()
-                    .map(|line| String::from(line))
+                    .map(String::from)
             )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_load_page_and_folds_error() {
+        assert_eq!(
+            Err(ParseError::new(13, 2, 3, "a y coordinate")),
+            load_page_and_folds(["6,10", "0,"].into_iter().map(String::from))
+        );
+
+        assert_eq!(
+            Err(ParseError::new(13, 3, 14, "a fold position")),
+            load_page_and_folds(["6,10", "", "fold along y=?"].into_iter().map(String::from))
         );
     }
 
@@ -231,8 +261,9 @@ mod test {
         let (page, folds) = load_page_and_folds(
             TEST_PAGE_AND_FOLDS_STRING
                 .lines()
-                .map(|line| String::from(line)),
-        );
+                .map(String::from),
+        )
+        .unwrap();
 
         let after_first_fold = page.apply_fold(&folds[0]);
         assert_eq!(17, after_first_fold.distinct_points());
@@ -246,8 +277,9 @@ mod test {
         let (page, folds) = load_page_and_folds(
             TEST_PAGE_AND_FOLDS_STRING
                 .lines()
-                .map(|line| String::from(line)),
-        );
+                .map(String::from),
+        )
+        .unwrap();
 
         let page = page.apply_folds(&folds);
         assert_eq!(16, page.distinct_points());
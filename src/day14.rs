@@ -1,26 +1,12 @@
+use crate::solution::{Input, Solution};
 use std::collections::HashMap;
 use std::str::FromStr;
-use std::{env, error};
+use std::error;
 
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let polymerizer = Polymerizer::from_str(std::fs::read_to_string(path)?.as_str()).unwrap();
-
-        println!("Element spread after 10 generations: {}", polymerizer.element_spread(10));
-        println!("Element spread after 40 generations: {}", polymerizer.element_spread(40));
-
-        Ok(())
-    } else {
-        Err("Usage: day14 INPUT_FILE_PATH".into())
-    }
-}
-
-type ElementPair = [char; 2];
+pub type ElementPair = [char; 2];
 
 #[derive(Debug, Eq, PartialEq)]
-struct Polymerizer {
+pub struct Polymerizer {
     template: Vec<char>,
     rules: HashMap<ElementPair, char>,
 }
@@ -109,6 +95,26 @@ impl FromStr for Polymerizer {
     }
 }
 
+pub struct Day14 {
+    polymerizer: Polymerizer,
+}
+
+impl Solution for Day14 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day14 {
+            polymerizer: Polymerizer::from_str(input.text()?)?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.polymerizer.element_spread(10).to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.polymerizer.element_spread(40).to_string())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
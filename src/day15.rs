@@ -0,0 +1,263 @@
+use crate::grid::{Grid, Neighborhood};
+use crate::parse_error::ParseError;
+use crate::search::{self, State};
+use crate::solution::{Input, Solution};
+use std::error;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+pub use crate::grid::RowCol;
+
+pub struct CaveMap {
+    risk_scores: Grid<u8>,
+}
+
+impl CaveMap {
+    // Returns the bottom-right position of a map made of `tiles` × `tiles` copies of this map
+    pub fn exit(&self, tiles: usize) -> RowCol {
+        (
+            (self.risk_scores.height() * tiles) - 1,
+            (self.risk_scores.width() * tiles) - 1,
+        )
+    }
+
+    pub fn path_risk_to_exit(&self, exit: RowCol) -> Option<u32> {
+        self.lowest_risk_path(exit).map(|(_, risk)| risk)
+    }
+
+    // Returns the positions along the lowest-risk path from the top-left corner to the given exit
+    // (including both ends) along with the total risk of that path, or `None` if there's no way to
+    // reach the exit
+    pub fn lowest_risk_path(&self, exit: RowCol) -> Option<(Vec<RowCol>, u32)> {
+        let start = CavePosition {
+            cave_map: self,
+            exit,
+            position: (0, 0),
+        };
+
+        search::dijkstra(start).path.map(|path| {
+            let positions = path.states.iter().map(|step| step.position).collect();
+            (positions, path.cost)
+        })
+    }
+
+    // Renders the risk scores of the map from the top-left corner to the given exit, with the
+    // positions along the given path in brackets
+    pub fn render_path(&self, exit: RowCol, path: &[RowCol]) -> String {
+        let on_path: HashSet<RowCol> = path.iter().copied().collect();
+        let mut rendered = String::new();
+
+        for row in 0..=exit.0 {
+            let line: String = (0..=exit.1)
+                .map(|col| {
+                    let risk = self.risk_score((row, col));
+
+                    if on_path.contains(&(row, col)) {
+                        format!("[{}]", risk)
+                    } else {
+                        format!(" {} ", risk)
+                    }
+                })
+                .collect();
+
+            rendered.push_str(line.trim_end());
+            rendered.push('\n');
+        }
+
+        rendered
+    }
+
+    fn risk_score(&self, position: RowCol) -> u32 {
+        let (row, col) = position;
+
+        let (height, width) = self.risk_scores.bounds();
+        let tile_distance = (row / height) + (col / width);
+
+        ((self.risk_scores[(row % height, col % width)] as u32 - 1 + tile_distance as u32) % 9) + 1
+    }
+}
+
+// A position within a (possibly tiled) cave map on the way to the given exit
+#[derive(Clone)]
+struct CavePosition<'a> {
+    cave_map: &'a CaveMap,
+    exit: RowCol,
+    position: RowCol,
+}
+
+impl State for CavePosition<'_> {
+    fn successors(&self) -> Vec<(Self, u32)> {
+        // The tiled map is never stored, so neighbors come from its bounds rather than a grid
+        Neighborhood::VON_NEUMANN
+            .neighbors(self.position, (self.exit.0 + 1, self.exit.1 + 1))
+            .map(|neighbor| {
+                let step = CavePosition {
+                    position: neighbor,
+                    ..*self
+                };
+
+                (step, self.cave_map.risk_score(neighbor))
+            })
+            .collect()
+    }
+
+    fn is_goal(&self) -> bool {
+        self.position == self.exit
+    }
+}
+
+// Positions are only ever compared within a single search of a single map, so there's no need to
+// compare (or hash) the map itself
+impl PartialEq for CavePosition<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.position == other.position
+    }
+}
+
+impl Eq for CavePosition<'_> {}
+
+impl Hash for CavePosition<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.position.hash(state);
+    }
+}
+
+impl FromStr for CaveMap {
+    type Err = ParseError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let risk_scores = Grid::parse(15, string, "a risk score digit", |c| {
+            c.to_digit(10).map(|risk| risk as u8)
+        })?;
+
+        Ok(CaveMap { risk_scores })
+    }
+}
+
+pub struct Day15 {
+    cave_map: CaveMap,
+}
+
+impl Solution for Day15 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day15 {
+            cave_map: CaveMap::from_str(input.text()?)?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        let risk = self
+            .cave_map
+            .path_risk_to_exit(self.cave_map.exit(1))
+            .ok_or("No path to the exit")?;
+
+        Ok(risk.to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        let risk = self
+            .cave_map
+            .path_risk_to_exit(self.cave_map.exit(5))
+            .ok_or("No path to the exit")?;
+
+        Ok(risk.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_MAP_STRING: &str = indoc! {"
+        1163751742
+        1381373672
+        2136511328
+        3694931569
+        7463417111
+        1319128137
+        1359912421
+        3125421639
+        1293138521
+        2311944581
+    "};
+
+    #[test]
+    fn test_cave_map_from_string_error() {
+        assert_eq!(
+            Some(ParseError::new(15, 2, 2, "a risk score digit")),
+            CaveMap::from_str("116\n1x8\n").err()
+        );
+    }
+
+    #[test]
+    fn test_path_risk_to_exit() {
+        let cave_map = CaveMap::from_str(TEST_MAP_STRING).unwrap();
+
+        assert_eq!(Some(40), cave_map.path_risk_to_exit((9, 9)));
+        assert_eq!(Some(315), cave_map.path_risk_to_exit((49, 49)));
+    }
+
+    #[test]
+    fn test_lowest_risk_path() {
+        let cave_map = CaveMap::from_str(TEST_MAP_STRING).unwrap();
+        let (path, risk) = cave_map.lowest_risk_path((9, 9)).unwrap();
+
+        assert_eq!(40, risk);
+        assert_eq!(Some(&(0, 0)), path.first());
+        assert_eq!(Some(&(9, 9)), path.last());
+
+        // The starting position isn't entered, so its risk doesn't count
+        assert_eq!(
+            risk,
+            path[1..].iter().map(|&position| cave_map.risk_score(position)).sum()
+        );
+
+        assert!(path.windows(2).all(|step| {
+            let ((row_a, col_a), (row_b, col_b)) = (step[0], step[1]);
+            row_a.abs_diff(row_b) + col_a.abs_diff(col_b) == 1
+        }));
+    }
+
+    #[test]
+    fn test_render_path() {
+        let cave_map = CaveMap::from_str(indoc! {"
+            191
+            121
+            991
+        "})
+        .unwrap();
+
+        let (path, risk) = cave_map.lowest_risk_path((2, 2)).unwrap();
+
+        assert_eq!(5, risk);
+        assert_eq!(vec![(0, 0), (1, 0), (1, 1), (1, 2), (2, 2)], path);
+
+        assert_eq!(
+            indoc! {"
+                [1] 9  1
+                [1][2][1]
+                 9  9 [1]
+            "},
+            cave_map.render_path((2, 2), &path)
+        );
+    }
+
+    #[test]
+    fn test_risk_score() {
+        let cave_map = CaveMap::from_str(TEST_MAP_STRING).unwrap();
+
+        assert_eq!(1, cave_map.risk_score((0, 0)));
+        assert_eq!(2, cave_map.risk_score((0, 10)));
+        assert_eq!(2, cave_map.risk_score((10, 0)));
+        assert_eq!(3, cave_map.risk_score((10, 10)));
+
+        assert_eq!(9, cave_map.risk_score((9, 4)));
+        assert_eq!(1, cave_map.risk_score((19, 4)));
+        assert_eq!(1, cave_map.risk_score((9, 14)));
+        assert_eq!(2, cave_map.risk_score((19, 14)));
+
+        assert_eq!(9, cave_map.risk_score((49, 49)));
+    }
+}
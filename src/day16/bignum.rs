@@ -0,0 +1,218 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul};
+
+// An unsigned integer of any size, just capable enough to evaluate BITS packets: addition,
+// multiplication, comparison and printing in decimal
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BigUint {
+    // Least significant first, with no trailing zero limbs (so zero has no limbs at all)
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Default::default()
+    }
+
+    // Builds a number from hexadecimal digits, most significant first
+    pub fn from_nibbles(nibbles: &[u8]) -> Self {
+        let mut limbs = vec![0u32; nibbles.len().div_ceil(8)];
+
+        for (i, &nibble) in nibbles.iter().rev().enumerate() {
+            limbs[i / 8] |= ((nibble & 0xf) as u32) << ((i % 8) * 4);
+        }
+
+        BigUint::normalized(limbs)
+    }
+
+    // Returns this number's hexadecimal digits, most significant first; zero has a single digit
+    pub fn nibbles(&self) -> Vec<u8> {
+        let mut nibbles: Vec<u8> = self
+            .limbs
+            .iter()
+            .flat_map(|&limb| (0..8).map(move |i| ((limb >> (i * 4)) & 0xf) as u8))
+            .collect();
+
+        while nibbles.len() > 1 && nibbles.last() == Some(&0) {
+            nibbles.pop();
+        }
+
+        if nibbles.is_empty() {
+            nibbles.push(0);
+        }
+
+        nibbles.reverse();
+        nibbles
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.len() {
+            0..=2 => Some(self.low_u64()),
+            _ => None,
+        }
+    }
+
+    // The lowest 64 bits of this number, as a `u64` would hold them after wrapping
+    pub fn low_u64(&self) -> u64 {
+        let limb = |i: usize| *self.limbs.get(i).unwrap_or(&0) as u64;
+        limb(0) | (limb(1) << 32)
+    }
+
+    fn normalized(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+
+        BigUint { limbs }
+    }
+
+    // Divides this number by a small divisor in place, returning the remainder
+    fn divide_in_place(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+
+        for limb in self.limbs.iter_mut().rev() {
+            let dividend = (remainder << 32) | *limb as u64;
+            *limb = (dividend / divisor as u64) as u32;
+            remainder = dividend % divisor as u64;
+        }
+
+        *self = BigUint::normalized(std::mem::take(&mut self.limbs));
+        remainder as u32
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        BigUint::normalized(vec![value as u32, (value >> 32) as u32])
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0u64;
+
+        for i in 0..self.limbs.len().max(other.limbs.len()) {
+            let sum = *self.limbs.get(i).unwrap_or(&0) as u64
+                + *other.limbs.get(i).unwrap_or(&0) as u64
+                + carry;
+
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+
+        limbs.push(carry as u32);
+        BigUint::normalized(limbs)
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let product = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        BigUint::normalized(limbs)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Peel off nine decimal digits at a time, least significant first
+        let mut remaining = self.clone();
+        let mut chunks = Vec::new();
+
+        loop {
+            chunks.push(remaining.divide_in_place(1_000_000_000));
+
+            if remaining.limbs.is_empty() {
+                break;
+            }
+        }
+
+        write!(f, "{}", chunks.pop().unwrap())?;
+
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:09}", chunk)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_nibbles() {
+        assert_eq!(BigUint::from(0x7e5), BigUint::from_nibbles(&[0x7, 0xe, 0x5]));
+        assert_eq!(BigUint::zero(), BigUint::from_nibbles(&[0, 0, 0]));
+        assert_eq!(vec![0], BigUint::zero().nibbles());
+        assert_eq!(vec![0x7, 0xe, 0x5], BigUint::from_nibbles(&[0, 0x7, 0xe, 0x5]).nibbles());
+
+        let big = BigUint::from_nibbles(&[1; 20]);
+        assert_eq!(vec![1; 20], big.nibbles());
+        assert_eq!(None, big.to_u64());
+        assert_eq!(0x1111111111111111, big.low_u64());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let max = BigUint::from(u64::MAX);
+        let one = BigUint::from(1);
+
+        let mut nibbles = vec![0; 17];
+        nibbles[0] = 1;
+
+        assert_eq!(BigUint::from_nibbles(&nibbles), &max + &one);
+        assert_eq!("340282366920938463426481119284349108225", (&max * &max).to_string());
+        assert_eq!(Some(6), (&BigUint::from(2) * &BigUint::from(3)).to_u64());
+        assert_eq!(BigUint::zero(), &BigUint::zero() * &max);
+
+        assert!(&max + &one > max);
+        assert!(BigUint::from(7) < BigUint::from(8));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0", BigUint::zero().to_string());
+        assert_eq!("2021", BigUint::from(2021).to_string());
+        assert_eq!(
+            "18446744073709551616",
+            (&BigUint::from(u64::MAX) + &BigUint::from(1)).to_string()
+        );
+        assert_eq!("1000000000", BigUint::from(1_000_000_000).to_string());
+    }
+}
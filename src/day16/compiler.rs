@@ -0,0 +1,278 @@
+use crate::day16::encoder::LengthType;
+use crate::day16::{Header, Packet};
+use crate::parse_error::ParseError;
+use std::error;
+
+// Type IDs for the operators `Packet::eval` understands
+const SUM: u8 = 0;
+const PRODUCT: u8 = 1;
+const MIN: u8 = 2;
+const MAX: u8 = 3;
+const GREATER_THAN: u8 = 5;
+const LESS_THAN: u8 = 6;
+const EQUAL_TO: u8 = 7;
+
+// Compiles an arithmetic expression into a packet. Expressions are made of integer literals, `+`
+// and `*` (which gather chains like `1 + 2 + 3` into a single packet), `min(...)` and `max(...)`
+// with any number of arguments, and at most one of `>`, `<` or `==` between two sums. Parentheses
+// group as usual. Every packet gets version 0.
+pub fn compile(source: &str) -> Result<Packet, ParseError> {
+    let mut parser = Parser {
+        source,
+        tokens: tokenize(source)?,
+        next: 0,
+    };
+
+    let packet = parser.comparison()?;
+
+    match parser.peek() {
+        Some(_) => Err(parser.error("an operator or the end of the expression")),
+        None => Ok(packet),
+    }
+}
+
+pub fn compile_to_hex(
+    source: &str,
+    length_type: Option<LengthType>,
+) -> Result<String, Box<dyn error::Error>> {
+    Ok(compile(source)?.to_hex(length_type)?)
+}
+
+// Splits an expression into numbers, words and symbols, each a slice of the source so errors can
+// point at them
+fn tokenize(source: &str) -> Result<Vec<&str>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+
+    while let Some(c) = source[start..].chars().next() {
+        let rest = &source[start..];
+
+        let len = if c.is_whitespace() {
+            start += c.len_utf8();
+            continue;
+        } else if c.is_ascii_digit() {
+            rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len())
+        } else if c.is_ascii_alphabetic() {
+            rest.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(rest.len())
+        } else if rest.starts_with("==") {
+            2
+        } else if "+*<>(),".contains(c) {
+            1
+        } else {
+            return Err(ParseError::at(16, source, rest, "a number, operator or parenthesis"));
+        };
+
+        tokens.push(&rest[..len]);
+        start += len;
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<&'a str>,
+    next: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.next).copied()
+    }
+
+    fn error(&self, expected: &str) -> ParseError {
+        match self.peek() {
+            Some(token) => ParseError::at(16, self.source, token, expected),
+            None => ParseError::at_end(16, self.source, expected),
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+        if self.peek() == Some(token) {
+            self.next += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("\"{}\"", token)))
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Packet, ParseError> {
+        let left = self.sum()?;
+
+        let type_id = match self.peek() {
+            Some(">") => GREATER_THAN,
+            Some("<") => LESS_THAN,
+            Some("==") => EQUAL_TO,
+            _ => return Ok(left),
+        };
+
+        self.next += 1;
+        let right = self.sum()?;
+
+        Ok(operator(type_id, vec![left, right]))
+    }
+
+    fn sum(&mut self) -> Result<Packet, ParseError> {
+        self.chain("+", SUM, Parser::product)
+    }
+
+    fn product(&mut self) -> Result<Packet, ParseError> {
+        self.chain("*", PRODUCT, Parser::primary)
+    }
+
+    // Parses one or more terms separated by `symbol`, making an operator packet if there's more
+    // than one
+    fn chain(
+        &mut self,
+        symbol: &str,
+        type_id: u8,
+        term: fn(&mut Self) -> Result<Packet, ParseError>,
+    ) -> Result<Packet, ParseError> {
+        let mut terms = vec![term(self)?];
+
+        while self.peek() == Some(symbol) {
+            self.next += 1;
+            terms.push(term(self)?);
+        }
+
+        if terms.len() == 1 {
+            Ok(terms.pop().unwrap())
+        } else {
+            Ok(operator(type_id, terms))
+        }
+    }
+
+    fn primary(&mut self) -> Result<Packet, ParseError> {
+        let token = self
+            .peek()
+            .ok_or_else(|| self.error("a number, \"(\", \"min\" or \"max\""))?;
+
+        match token {
+            "(" => {
+                self.next += 1;
+                let packet = self.comparison()?;
+                self.expect(")")?;
+
+                Ok(packet)
+            }
+            "min" | "max" => {
+                self.next += 1;
+                self.expect("(")?;
+
+                let mut arguments = vec![self.comparison()?];
+
+                while self.peek() == Some(",") {
+                    self.next += 1;
+                    arguments.push(self.comparison()?);
+                }
+
+                self.expect(")")?;
+
+                Ok(operator(if token == "min" { MIN } else { MAX }, arguments))
+            }
+            _ if token.starts_with(|c: char| c.is_ascii_digit()) => {
+                let value = token
+                    .parse()
+                    .map_err(|_| self.error("a number that fits in 64 bits"))?;
+
+                self.next += 1;
+
+                Ok(Packet::Literal {
+                    header: Header::new(0, Packet::LITERAL_TYPE_ID),
+                    value,
+                })
+            }
+            _ => Err(self.error("a number, \"(\", \"min\" or \"max\"")),
+        }
+    }
+}
+
+fn operator(type_id: u8, sub_packets: Vec<Packet>) -> Packet {
+    Packet::Operator {
+        header: Header::new(0, type_id),
+        sub_packets,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_compile() {
+        let packet = compile("1 + 2 * 3 + 4").unwrap();
+
+        assert_eq!("sum(1, product(2, 3), 4)", packet.to_string());
+        assert_eq!(11, packet.eval());
+
+        assert_eq!(
+            "eq(sum(1, 3), product(2, 2))",
+            compile("1 + 3 == 2 * 2").unwrap().to_string()
+        );
+
+        assert_eq!(
+            "product(sum(1, 2), max(3, lt(4, 5), min(6)))",
+            compile("(1+2)*max(3, 4 < 5, min(6))").unwrap().to_string()
+        );
+
+        assert_eq!(1, compile("(3 > 2) > (1 == 2)").unwrap().eval());
+        assert_eq!(u64::MAX, compile("18446744073709551615").unwrap().eval());
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        assert_eq!(
+            Err(ParseError::new(16, 1, 5, "a number, operator or parenthesis")),
+            compile("1 + -2")
+        );
+
+        assert_eq!(
+            Err(ParseError::new(16, 1, 7, "an operator or the end of the expression")),
+            compile("1 < 2 < 3")
+        );
+
+        assert_eq!(
+            Err(ParseError::new(16, 1, 8, "a number, \"(\", \"min\" or \"max\"")),
+            compile("1 + 2 +")
+        );
+
+        assert_eq!(
+            Err(ParseError::new(16, 1, 10, "\")\"")),
+            compile("min(1, 2 3)")
+        );
+
+        assert_eq!(
+            Err(ParseError::new(16, 1, 1, "a number, \"(\", \"min\" or \"max\"")),
+            compile("sum(1, 2)")
+        );
+
+        assert_eq!(
+            Err(ParseError::new(16, 1, 1, "a number that fits in 64 bits")),
+            compile("18446744073709551616")
+        );
+    }
+
+    #[test]
+    fn test_compile_to_hex() {
+        for (source, value) in [
+            ("1 + 2", 3),
+            ("6 * 9", 54),
+            ("min(7, 8, 9)", 7),
+            ("max(7, 8, 9)", 9),
+            ("5 < 15", 1),
+            ("5 > 15", 0),
+            ("5 == 15", 0),
+            ("1 + 3 == 2 * 2", 1),
+            ("3 + 6 * 9 + min(7, 8, 9)", 64),
+        ] {
+            for length_type in [None, Some(LengthType::BitCount), Some(LengthType::PacketCount)] {
+                let hex = compile_to_hex(source, length_type).unwrap();
+                assert_eq!(value, Packet::from_hex(&hex).unwrap().eval());
+            }
+        }
+
+        // The same packets as the puzzle's "1 + 2" example, but with every version set to 0
+        assert_eq!("0200840882", compile_to_hex("1 + 2", None).unwrap());
+        assert!(compile_to_hex("1 +", None).is_err());
+    }
+}
@@ -0,0 +1,355 @@
+use crate::day16::bignum::BigUint;
+use crate::day16::{DecodeReason, Header, Packet};
+use std::error;
+use std::fmt::{Display, Formatter};
+
+// How an operator packet says where its sub-packets end
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LengthType {
+    // Length type 0: a 15-bit count of the bits in the sub-packets
+    BitCount,
+    // Length type 1: an 11-bit count of the sub-packets
+    PacketCount,
+}
+
+impl LengthType {
+    const BIT_COUNT_BITS: usize = 15;
+    const PACKET_COUNT_BITS: usize = 11;
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EncodeError {
+    // Versions and type IDs only get three bits each
+    HeaderOutOfRange(u8, u8),
+    // Literals need the literal type ID, and operators need anything else
+    MismatchedTypeId(u8),
+    // An operator with a number of sub-packets (type ID, then count) it can't be decoded with
+    BadArity(u8, usize),
+    TooManySubPackets(usize),
+    TooManySubPacketBits(usize),
+}
+
+impl Display for EncodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncodeError::HeaderOutOfRange(version, type_id) => write!(
+                f,
+                "Version {} or type ID {} doesn't fit in three bits",
+                version, type_id
+            ),
+            EncodeError::MismatchedTypeId(type_id) => {
+                write!(f, "Type ID {} doesn't match the kind of packet", type_id)
+            }
+            EncodeError::BadArity(type_id, count) => {
+                write!(f, "Operator with type ID {} can't have {} sub-packets", type_id, count)
+            }
+            EncodeError::TooManySubPackets(count) => {
+                write!(f, "{} sub-packets don't fit in an 11-bit packet count", count)
+            }
+            EncodeError::TooManySubPacketBits(bits) => {
+                write!(f, "{} bits of sub-packets don't fit in a 15-bit bit count", bits)
+            }
+        }
+    }
+}
+
+impl error::Error for EncodeError {}
+
+// The opposite of a `BitStream`: collects bits from most to least significant, padding the last
+// byte with zeroes
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    position: usize,
+}
+
+impl BitWriter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // Writes the lowest `n_bits` bits of the given value
+    pub fn push_bits(&mut self, value: u64, n_bits: usize) {
+        for bit in (0..n_bits).rev() {
+            self.push_bit(((value >> bit) & 1) as u8);
+        }
+    }
+
+    fn push_bit(&mut self, bit: u8) {
+        if self.position.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+
+        *self.bytes.last_mut().unwrap() |= bit << (7 - self.position % 8);
+        self.position += 1;
+    }
+
+    pub fn append(&mut self, other: &BitWriter) {
+        for position in 0..other.position {
+            self.push_bit((other.bytes[position / 8] >> (7 - position % 8)) & 1);
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl Packet {
+    // Encodes this packet as a hexadecimal transmission. Operators use the given length type, or
+    // if there's no preference, whichever fits in the fewest bits.
+    pub fn to_hex(&self, length_type: Option<LengthType>) -> Result<String, EncodeError> {
+        let mut bit_writer = BitWriter::new();
+        self.write_to(&mut bit_writer, length_type)?;
+
+        Ok(hex::encode_upper(bit_writer.into_bytes()))
+    }
+
+    pub fn write_to(
+        &self,
+        bit_writer: &mut BitWriter,
+        length_type: Option<LengthType>,
+    ) -> Result<(), EncodeError> {
+        match self {
+            Packet::Literal { header, value } => {
+                Packet::write_literal(bit_writer, header, &BigUint::from(*value))?
+            }
+            Packet::BigLiteral { header, value } => {
+                Packet::write_literal(bit_writer, header, value)?
+            }
+            Packet::Operator {
+                header,
+                sub_packets,
+            } => {
+                if header.type_id == Packet::LITERAL_TYPE_ID {
+                    return Err(EncodeError::MismatchedTypeId(header.type_id));
+                }
+
+                header.write_to(bit_writer)?;
+
+                if DecodeReason::check_operator(header.type_id, sub_packets.len()).is_some() {
+                    return Err(EncodeError::BadArity(header.type_id, sub_packets.len()));
+                }
+
+                let mut sub_packet_writer = BitWriter::new();
+
+                for sub_packet in sub_packets {
+                    sub_packet.write_to(&mut sub_packet_writer, length_type)?;
+                }
+
+                let sub_packet_bits = sub_packet_writer.position();
+                let fits_packet_count = sub_packets.len() < 1 << LengthType::PACKET_COUNT_BITS;
+                let fits_bit_count = sub_packet_bits < 1 << LengthType::BIT_COUNT_BITS;
+
+                let length_type = match length_type {
+                    Some(length_type) => length_type,
+                    None if fits_packet_count || !fits_bit_count => LengthType::PacketCount,
+                    None => LengthType::BitCount,
+                };
+
+                match length_type {
+                    LengthType::BitCount if fits_bit_count => {
+                        bit_writer.push_bits(0, 1);
+                        bit_writer.push_bits(sub_packet_bits as u64, LengthType::BIT_COUNT_BITS);
+                    }
+                    LengthType::BitCount => {
+                        return Err(EncodeError::TooManySubPacketBits(sub_packet_bits))
+                    }
+                    LengthType::PacketCount if fits_packet_count => {
+                        bit_writer.push_bits(1, 1);
+                        let count = sub_packets.len() as u64;
+                        bit_writer.push_bits(count, LengthType::PACKET_COUNT_BITS);
+                    }
+                    LengthType::PacketCount => {
+                        return Err(EncodeError::TooManySubPackets(sub_packets.len()))
+                    }
+                }
+
+                bit_writer.append(&sub_packet_writer);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_literal(
+        bit_writer: &mut BitWriter,
+        header: &Header,
+        value: &BigUint,
+    ) -> Result<(), EncodeError> {
+        if header.type_id != Packet::LITERAL_TYPE_ID {
+            return Err(EncodeError::MismatchedTypeId(header.type_id));
+        }
+
+        header.write_to(bit_writer)?;
+
+        // Use as few nibbles as it takes to hold the value, but always at least one
+        let nibbles = value.nibbles();
+
+        for (i, &nibble) in nibbles.iter().enumerate() {
+            let has_more = if i + 1 < nibbles.len() { Packet::LITERAL_HAS_MORE_BIT } else { 0 };
+            bit_writer.push_bits((has_more | nibble) as u64, 5);
+        }
+
+        Ok(())
+    }
+}
+
+impl Header {
+    fn write_to(&self, bit_writer: &mut BitWriter) -> Result<(), EncodeError> {
+        if self.version > 7 || self.type_id > 7 {
+            return Err(EncodeError::HeaderOutOfRange(self.version, self.type_id));
+        }
+
+        bit_writer.push_bits(self.version as u64, 3);
+        bit_writer.push_bits(self.type_id as u64, 3);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day16::BitStream;
+
+    const EXAMPLES: [&str; 17] = [
+        "D2FE28",
+        "38006F45291200",
+        "EE00D40C823060",
+        "8A004A801A8002F478",
+        "620080001611562C8802118E34",
+        "C0015000016115A2E0802F182340",
+        "A0016C880162017C3686B18A3D4780",
+        "C200B40A82",
+        "04005AC33890",
+        "880086C3E88112",
+        "CE00C43D881120",
+        "D8005AC2A8F0",
+        "F600BC2D8F",
+        "9C005AC2F8F0",
+        "9C0141080250320F1802104A08",
+        "0000000000",
+        "E20044FFFFFFFFFFFFFFFFFFEF",
+    ];
+
+    fn literal(version: u8, value: u64) -> Packet {
+        Packet::Literal {
+            header: Header::new(version, Packet::LITERAL_TYPE_ID),
+            value,
+        }
+    }
+
+    #[test]
+    fn test_bit_writer() {
+        let mut bit_writer = BitWriter::new();
+        bit_writer.push_bits(0b110, 3);
+        bit_writer.push_bits(0b100, 3);
+        bit_writer.push_bits(0b10111, 5);
+
+        let mut other = BitWriter::new();
+        other.push_bits(0b11110, 5);
+        other.push_bits(0b00101, 5);
+
+        bit_writer.append(&other);
+
+        assert_eq!(21, bit_writer.position());
+        assert_eq!(vec![0xd2, 0xfe, 0x28], bit_writer.into_bytes());
+    }
+
+    #[test]
+    fn test_encode_literal() {
+        assert_eq!("D2FE28", literal(6, 2021).to_hex(None).unwrap());
+        assert_eq!("1000", literal(0, 0).to_hex(None).unwrap());
+        assert_eq!(
+            "E20044FFFFFFFFFFFFFFFFFFEF",
+            Packet::Operator {
+                header: Header::new(7, 0),
+                sub_packets: vec![literal(0, u64::MAX)],
+            }
+            .to_hex(None)
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_encode_operator() {
+        let packet = Packet::from_hex("38006F45291200").unwrap();
+        assert_eq!("38006F45291200", packet.to_hex(Some(LengthType::BitCount)).unwrap());
+
+        let packet = Packet::from_hex("EE00D40C823060").unwrap();
+        assert_eq!("EE00D40C823060", packet.to_hex(Some(LengthType::PacketCount)).unwrap());
+        assert_eq!("EE00D40C823060", packet.to_hex(None).unwrap());
+    }
+
+    #[test]
+    fn test_encode_errors() {
+        assert_eq!(
+            Err(EncodeError::HeaderOutOfRange(8, 4)),
+            literal(8, 1).to_hex(None)
+        );
+
+        assert_eq!(
+            Err(EncodeError::MismatchedTypeId(0)),
+            Packet::Literal {
+                header: Header::new(1, 0),
+                value: 1
+            }
+            .to_hex(None)
+        );
+
+        // Comparisons need exactly two operands, so a transmission with one wouldn't decode
+        let packet = Packet::Operator {
+            header: Header::new(6, 5),
+            sub_packets: vec![literal(1, 2)],
+        };
+
+        assert_eq!(Err(EncodeError::BadArity(5, 1)), packet.to_hex(None));
+
+        let packet = Packet::Operator {
+            header: Header::new(1, 0),
+            sub_packets: (0..2048).map(|_| literal(1, 0)).collect(),
+        };
+
+        assert_eq!(
+            Err(EncodeError::TooManySubPackets(2048)),
+            packet.to_hex(Some(LengthType::PacketCount))
+        );
+
+        // Too many sub-packets to count, but few enough bits to count those instead
+        let hex = packet.to_hex(None).unwrap();
+        let mut bit_stream = BitStream::from_hex(&hex).unwrap();
+        bit_stream.next_bits(6).unwrap();
+
+        assert_eq!(Ok(0), bit_stream.next_bits(1));
+        assert_eq!(packet, Packet::from_hex(&hex).unwrap());
+
+        let packet = Packet::Operator {
+            header: Header::new(1, 0),
+            sub_packets: (0..3000).map(|value| literal(1, value)).collect(),
+        };
+
+        assert_eq!(
+            // Six header bits each, plus a five-bit group for each nibble of 0..16, 16..256 and
+            // 256..3000
+            Err(EncodeError::TooManySubPacketBits(3000 * 6 + 5 * (16 + 240 * 2 + 2744 * 3))),
+            packet.to_hex(Some(LengthType::BitCount))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for example in EXAMPLES {
+            let packet = Packet::from_hex(example).unwrap();
+
+            for length_type in [None, Some(LengthType::BitCount), Some(LengthType::PacketCount)] {
+                let hex = packet.to_hex(length_type).unwrap();
+                assert_eq!(packet, Packet::from_hex(&hex).unwrap());
+            }
+        }
+    }
+}
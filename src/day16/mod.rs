@@ -0,0 +1,1052 @@
+use crate::parse_error::ParseError;
+use crate::solution::{Input, Solution};
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use self::Packet::Operator;
+use hex::FromHexError;
+use self::bignum::BigUint;
+pub mod bignum;
+pub mod compiler;
+pub mod encoder;
+pub mod stream;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Header {
+    version: u8,
+    type_id: u8,
+}
+
+impl Header {
+    pub fn new(version: u8, type_id: u8) -> Self {
+        Header { version, type_id }
+    }
+
+    pub fn next_from_bit_stream(bit_stream: &mut BitStream) -> Result<Self, DecodeError> {
+        Ok(Header {
+            version: bit_stream.next_bits(3)? as u8,
+            type_id: bit_stream.next_bits(3)? as u8,
+        })
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Packet {
+    Literal {
+        header: Header,
+        value: u64,
+    },
+
+    // A literal too big for a `u64`; the decoder only makes these when a `Literal` won't do
+    BigLiteral {
+        header: Header,
+        value: BigUint,
+    },
+
+    Operator {
+        header: Header,
+        sub_packets: Vec<Packet>,
+    },
+}
+
+impl Packet {
+    const LITERAL_TYPE_ID: u8 = 4;
+    const LITERAL_HAS_MORE_BIT: u8 = 0b00010000;
+    const LITERAL_NIBBLE_MASK: u8 = 0b00001111;
+
+    pub fn from_hex(hex: &str) -> Result<Self, ParseError> {
+        let bit_stream = BitStream::from_hex(hex).map_err(|error| match error {
+            FromHexError::InvalidHexCharacter { index, .. } => {
+                ParseError::new(16, 1, index + 1, "a hexadecimal digit")
+            }
+            _ => ParseError::at_end(16, hex, "an even number of hexadecimal digits"),
+        })?;
+
+        // Point at the hex digit holding the offending bit
+        Packet::decode(bit_stream)
+            .map_err(|error| ParseError::new(16, 1, error.offset / 4 + 1, error.reason.expected()))
+    }
+
+    // Decodes a whole transmission: a single packet followed by nothing but zero padding
+    pub fn decode(mut bit_stream: BitStream) -> Result<Self, DecodeError> {
+        let mut decoder = Decoder {
+            bit_stream: &mut bit_stream,
+            problems: None,
+        };
+
+        let packet = decoder.packet()?;
+        decoder.padding()?;
+
+        Ok(packet)
+    }
+
+    // Like `decode`, but carries on past any problem that still leaves us able to read the rest of
+    // the transmission, and reports every problem it finds. Running out of bits stops decoding
+    // altogether, in which case there's no packet. There's no packet if any operator has the wrong
+    // number of sub-packets, either, since there'd be no way to evaluate it.
+    pub fn decode_lenient(mut bit_stream: BitStream) -> (Option<Self>, Vec<DecodeError>) {
+        let mut decoder = Decoder {
+            bit_stream: &mut bit_stream,
+            problems: Some(Vec::new()),
+        };
+
+        let result = decoder.packet().and_then(|packet| {
+            decoder.padding()?;
+            Ok(packet)
+        });
+
+        let mut problems = decoder.problems.take().unwrap_or_default();
+
+        let has_bad_arity = problems
+            .iter()
+            .any(|problem| matches!(problem.reason, DecodeReason::BadArity { .. }));
+
+        match result {
+            Ok(_) if has_bad_arity => (None, problems),
+            Ok(packet) => (Some(packet), problems),
+            Err(error) => {
+                problems.push(error);
+                (None, problems)
+            }
+        }
+    }
+
+    pub fn version_sum(&self) -> u32 {
+        match self {
+            Packet::Literal { header, value: _ } => header.version as u32,
+            Packet::BigLiteral { header, value: _ } => header.version as u32,
+            Packet::Operator {
+                header,
+                sub_packets,
+            } => {
+                header.version as u32
+                    + sub_packets
+                        .iter()
+                        .map(|sub_packet| sub_packet.version_sum())
+                        .sum::<u32>()
+            }
+        }
+    }
+
+    // Evaluates this packet in `u64`s, which wrap (or panic in debug builds) on overflow; big
+    // literals contribute their lowest 64 bits
+    pub fn eval(&self) -> u64 {
+        match self {
+            Packet::Literal { header: _, value} => *value,
+            Packet::BigLiteral { header: _, value } => value.low_u64(),
+            Operator { header, sub_packets } => {
+                let values: Vec<u64> = sub_packets.iter()
+                    .map(|sub_packet| sub_packet.eval())
+                    .collect();
+
+                Packet::apply(header.type_id, &values)
+            }
+        }
+    }
+
+    // Evaluates this packet in `u64`s like `eval`, but stops at the first value that doesn't fit
+    // and reports where it came from
+    pub fn checked_eval(&self) -> Result<u64, Overflow> {
+        let mut path = Vec::new();
+
+        self.checked_eval_at(&mut path).ok_or(Overflow { path })
+    }
+
+    // Leaves `path` pointing at the offending packet if evaluation overflows
+    fn checked_eval_at(&self, path: &mut Vec<usize>) -> Option<u64> {
+        match self {
+            Packet::Literal { header: _, value } => Some(*value),
+            Packet::BigLiteral { header: _, value } => value.to_u64(),
+            Operator { header, sub_packets } => {
+                let mut values = Vec::with_capacity(sub_packets.len());
+
+                for (i, sub_packet) in sub_packets.iter().enumerate() {
+                    path.push(i);
+                    values.push(sub_packet.checked_eval_at(path)?);
+                    path.pop();
+                }
+
+                match header.type_id {
+                    0 => values.iter().try_fold(0u64, |sum, &value| sum.checked_add(value)),
+                    1 => values.iter().try_fold(1u64, |product, &value| product.checked_mul(value)),
+                    type_id => Some(Packet::apply(type_id, &values)),
+                }
+            }
+        }
+    }
+
+    // Evaluates this packet without any limit on the size of its values
+    pub fn eval_big(&self) -> BigUint {
+        match self {
+            Packet::Literal { header: _, value } => BigUint::from(*value),
+            Packet::BigLiteral { header: _, value } => value.clone(),
+            Operator { header, sub_packets } => {
+                let values: Vec<BigUint> = sub_packets
+                    .iter()
+                    .map(|sub_packet| sub_packet.eval_big())
+                    .collect();
+
+                match header.type_id {
+                    0 => values.iter().fold(BigUint::zero(), |sum, value| &sum + value),
+                    1 => values.iter().fold(BigUint::from(1), |product, value| &product * value),
+                    2 => values.into_iter().min().unwrap(),
+                    3 => values.into_iter().max().unwrap(),
+                    5 => BigUint::from((values[0] > values[1]) as u64),
+                    6 => BigUint::from((values[0] < values[1]) as u64),
+                    7 => BigUint::from((values[0] == values[1]) as u64),
+                    _ => unreachable!()
+                }
+            }
+        }
+    }
+
+    // Finds a packet by the indices of the sub-packets leading to it, as in an `Overflow`
+    pub fn at_path(&self, path: &[usize]) -> Option<&Packet> {
+        match (path.split_first(), self) {
+            (None, _) => Some(self),
+            (Some((&i, rest)), Operator { header: _, sub_packets }) => {
+                sub_packets.get(i)?.at_path(rest)
+            }
+            (Some(_), _) => None,
+        }
+    }
+
+    // Evaluates this packet, recording the operands and result of each operator along the way
+    pub fn trace(&self) -> Trace {
+        let mut entries = Vec::new();
+        let value = self.trace_into(0, &mut entries);
+
+        Trace { entries, value }
+    }
+
+    fn trace_into(&self, depth: usize, entries: &mut Vec<TraceEntry>) -> u64 {
+        match self {
+            Packet::Literal { header: _, value } => *value,
+            Packet::BigLiteral { header: _, value } => value.low_u64(),
+            Operator { header, sub_packets } => {
+                // Hold this operator's place so it comes before its sub-packets in the trace
+                let index = entries.len();
+                entries.push(TraceEntry {
+                    depth,
+                    header: *header,
+                    operands: Vec::new(),
+                    value: 0,
+                });
+
+                let operands: Vec<u64> = sub_packets
+                    .iter()
+                    .map(|sub_packet| sub_packet.trace_into(depth + 1, entries))
+                    .collect();
+
+                let value = Packet::apply(header.type_id, &operands);
+
+                entries[index].operands = operands;
+                entries[index].value = value;
+
+                value
+            }
+        }
+    }
+
+    fn apply(type_id: u8, values: &[u64]) -> u64 {
+        match type_id {
+            // Sum
+            0 => values.iter().sum(),
+            // Product
+            1 => values.iter().product(),
+            // Min
+            2 => *values.iter().min().unwrap(),
+            // Max
+            3 => *values.iter().max().unwrap(),
+            // Greater than
+            5 => (values[0] > values[1]) as u64,
+            // Less than
+            6 => (values[0] < values[1]) as u64,
+            // Equal to
+            7 => (values[0] == values[1]) as u64,
+            _ => unreachable!()
+        }
+    }
+
+    fn operator_name(type_id: u8) -> String {
+        match type_id {
+            0 => "sum".to_string(),
+            1 => "product".to_string(),
+            2 => "min".to_string(),
+            3 => "max".to_string(),
+            5 => "gt".to_string(),
+            6 => "lt".to_string(),
+            7 => "eq".to_string(),
+            _ => format!("op{}", type_id),
+        }
+    }
+}
+
+// Writes packets as expressions like `sum(3, product(6, 9))`; the alternate form (`{:#}`) tags
+// each packet with its version, as in `sum@1(3@6, product@0(6@5, 9@3))`
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let version = |f: &mut Formatter<'_>, header: &Header| {
+            if f.alternate() {
+                write!(f, "@{}", header.version)
+            } else {
+                Ok(())
+            }
+        };
+
+        match self {
+            Packet::Literal { header, value } => {
+                write!(f, "{}", value)?;
+                version(f, header)
+            }
+            Packet::BigLiteral { header, value } => {
+                write!(f, "{}", value)?;
+                version(f, header)
+            }
+            Operator { header, sub_packets } => {
+                write!(f, "{}", Packet::operator_name(header.type_id))?;
+                version(f, header)?;
+                write!(f, "(")?;
+
+                for (i, sub_packet) in sub_packets.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    sub_packet.fmt(f)?;
+                }
+
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+// Where `Packet::checked_eval` first found a value too big for a `u64`: the index of each
+// sub-packet on the way from the outermost packet to the offending one
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Overflow {
+    pub path: Vec<usize>,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Value overflows 64 bits at packet root")?;
+
+        for i in &self.path {
+            write!(f, "[{}]", i)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl error::Error for Overflow {}
+
+// A single operator's evaluation, with the values of its sub-packets
+#[derive(Debug, Eq, PartialEq)]
+pub struct TraceEntry {
+    pub depth: usize,
+    pub header: Header,
+    pub operands: Vec<u64>,
+    pub value: u64,
+}
+
+impl Display for TraceEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let operands: Vec<String> =
+            self.operands.iter().map(|operand| operand.to_string()).collect();
+
+        write!(
+            f,
+            "{:indent$}{}({}) = {}  [version {}]",
+            "",
+            Packet::operator_name(self.header.type_id),
+            operands.join(", "),
+            self.value,
+            self.header.version,
+            indent = self.depth * 2
+        )
+    }
+}
+
+// Every operator evaluated on the way to a packet's value, outermost first, with each operator's
+// sub-packets indented beneath it
+#[derive(Debug, Eq, PartialEq)]
+pub struct Trace {
+    pub entries: Vec<TraceEntry>,
+    pub value: u64,
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{}", entry)?;
+        }
+
+        write!(f, "= {}", self.value)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DecodeReason {
+    // The transmission ran out of bits partway through a packet
+    Truncated,
+    // Comparisons need exactly two sub-packets, and `min` and `max` need at least one
+    BadArity { type_id: u8, sub_packets: usize },
+    // An operator's sub-packets didn't end where its bit count said they would
+    BitCountMismatch { declared: usize, actual: usize },
+    NonzeroPadding,
+}
+
+impl DecodeReason {
+    // Finds what's wrong (if anything) with an operator that has the given number of sub-packets
+    fn check_operator(type_id: u8, sub_packets: usize) -> Option<Self> {
+        match (type_id, sub_packets) {
+            (0 | 1, _) | (5..=7, 2) => None,
+            (2 | 3, count) if count > 0 => None,
+            (type_id, count) => Some(DecodeReason::BadArity {
+                type_id,
+                sub_packets: count,
+            }),
+        }
+    }
+
+    // Describes what a well-formed transmission would have had instead, for `ParseError`
+    fn expected(&self) -> String {
+        match self {
+            DecodeReason::Truncated => "the rest of the packet".to_string(),
+            DecodeReason::BadArity { type_id, .. } if *type_id >= 5 => {
+                "exactly two sub-packets".to_string()
+            }
+            DecodeReason::BadArity { .. } => "at least one sub-packet".to_string(),
+            DecodeReason::BitCountMismatch { declared, .. } => {
+                format!("sub-packets totalling {} bits", declared)
+            }
+            DecodeReason::NonzeroPadding => "zero padding".to_string(),
+        }
+    }
+}
+
+impl Display for DecodeReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeReason::Truncated => write!(f, "transmission ends partway through a packet"),
+            DecodeReason::BadArity {
+                type_id,
+                sub_packets,
+            } => write!(
+                f,
+                "{} has {} sub-packets, but needs {}",
+                Packet::operator_name(*type_id),
+                sub_packets,
+                self.expected()
+            ),
+            DecodeReason::BitCountMismatch { declared, actual } => write!(
+                f,
+                "sub-packets take {} bits instead of the declared {}",
+                actual, declared
+            ),
+            DecodeReason::NonzeroPadding => write!(f, "padding after the packet isn't all zeroes"),
+        }
+    }
+}
+
+// A problem with a transmission, along with the offset (in bits) of the packet or bit it concerns
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DecodeError {
+    pub offset: usize,
+    pub reason: DecodeReason,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Bit {}: {}", self.offset, self.reason)
+    }
+}
+
+impl error::Error for DecodeError {}
+
+struct Decoder<'a> {
+    bit_stream: &'a mut BitStream,
+    // Problems noted on the way in lenient mode; strict decoding stops at the first one instead
+    problems: Option<Vec<DecodeError>>,
+}
+
+impl Decoder<'_> {
+    fn report(&mut self, offset: usize, reason: DecodeReason) -> Result<(), DecodeError> {
+        let error = DecodeError { offset, reason };
+
+        match &mut self.problems {
+            Some(problems) => {
+                problems.push(error);
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+
+    fn packet(&mut self) -> Result<Packet, DecodeError> {
+        let offset = self.bit_stream.position();
+        let header = Header::next_from_bit_stream(self.bit_stream)?;
+
+        match header.type_id {
+            Packet::LITERAL_TYPE_ID => {
+                // Literal! Read five-bit chunks until we get a "last chunk" bit.
+                let mut nibbles = Vec::new();
+
+                loop {
+                    let next_nibble = self.bit_stream.next_bits(5)? as u8;
+                    nibbles.push(next_nibble & Packet::LITERAL_NIBBLE_MASK);
+
+                    if next_nibble & Packet::LITERAL_HAS_MORE_BIT == 0 {
+                        break;
+                    }
+                }
+
+                let value = BigUint::from_nibbles(&nibbles);
+
+                match value.to_u64() {
+                    Some(value) => Ok(Packet::Literal { header, value }),
+                    None => Ok(Packet::BigLiteral { header, value }),
+                }
+            }
+            _ => {
+                // Operator
+                let length_type = self.bit_stream.next_bits(1)?;
+
+                let mut sub_packets = Vec::new();
+
+                if length_type == 0 {
+                    // 15-bit bit count
+                    let target_bit_count = self.bit_stream.next_bits(15)? as usize;
+                    let start_position = self.bit_stream.position();
+                    let target_position = start_position + target_bit_count;
+
+                    while self.bit_stream.position() < target_position {
+                        sub_packets.push(self.packet()?);
+                    }
+
+                    if self.bit_stream.position() != target_position {
+                        let reason = DecodeReason::BitCountMismatch {
+                            declared: target_bit_count,
+                            actual: self.bit_stream.position() - start_position,
+                        };
+
+                        self.report(offset, reason)?;
+                    }
+                } else {
+                    // 11-bit packet count
+                    let target_packet_count = self.bit_stream.next_bits(11)?;
+
+                    for _ in 0..target_packet_count {
+                        sub_packets.push(self.packet()?);
+                    }
+                }
+
+                let problem = DecodeReason::check_operator(header.type_id, sub_packets.len());
+
+                if let Some(reason) = problem {
+                    self.report(offset, reason)?;
+                }
+
+                Ok(Operator {
+                    header,
+                    sub_packets,
+                })
+            }
+        }
+    }
+
+    // Checks that everything after the outermost packet is zero, reporting the first one bit
+    fn padding(&mut self) -> Result<(), DecodeError> {
+        while self.bit_stream.remaining() > 0 {
+            let offset = self.bit_stream.position();
+
+            if self.bit_stream.next_bits(1)? != 0 {
+                return self.report(offset, DecodeReason::NonzeroPadding);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct BitStream {
+    bytes: Vec<u8>,
+    position: usize,
+}
+
+impl BitStream {
+    const BIT_MASK: [u8; 8] = [
+        0b10000000,
+        0b01000000,
+        0b00100000,
+        0b00010000,
+        0b00001000,
+        0b00000100,
+        0b00000010,
+        0b00000001,
+    ];
+
+    pub fn from_hex(hex_string: &str) -> Result<Self, FromHexError> {
+        Ok(BitStream::new(hex::decode(hex_string)?))
+    }
+
+    pub fn new(bytes: Vec<u8>) -> Self {
+        BitStream { bytes, position: 0 }
+    }
+
+    pub fn next_bits(&mut self, n_bits: usize) -> Result<u32, DecodeError> {
+        if n_bits > self.remaining() {
+            return Err(DecodeError {
+                offset: self.position,
+                reason: DecodeReason::Truncated,
+            });
+        }
+
+        let mut next_bits = 0u32;
+
+        for _ in 0..n_bits {
+            next_bits <<= 1;
+            next_bits |= self.next_bit() as u32;
+        }
+
+        Ok(next_bits)
+    }
+
+    fn next_bit(&mut self) -> u8 {
+        let byte_index = self.position / 8;
+        let bit_offset = self.position % 8;
+
+        self.position += 1;
+
+        (self.bytes[byte_index] & BitStream::BIT_MASK[bit_offset]) >> (7 - bit_offset)
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() * 8 - self.position
+    }
+}
+
+pub struct Day16 {
+    packet: Packet,
+}
+
+impl Solution for Day16 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day16 {
+            packet: Packet::from_hex(input.text()?.trim())?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.packet.version_sum().to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        // Real transmissions fit in 64 bits, but there's no harm in being sure
+        Ok(self.packet.eval_big().to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use self::Packet::{BigLiteral, Literal};
+    use crate::day16::encoder::BitWriter;
+    use indoc::indoc;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn test_bit_stream_from_hex() {
+        assert_eq!(
+            BitStream::new(vec![0xd2, 0xfe, 0x28]),
+            BitStream::from_hex("D2FE28").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_bit() {
+        let mut bit_stream = BitStream::new(vec![0xd2, 0xfe, 0x28]);
+        let mut expected = VecDeque::from([
+            1, 1, 0, 1, 0, 0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 1, 0, 1, 0, 0, 0,
+        ]);
+
+        while let Some(expected_bit) = expected.pop_front() {
+            assert_eq!(expected_bit, bit_stream.next_bit());
+        }
+    }
+
+    #[test]
+    fn test_next_bits() {
+        let mut bit_stream = BitStream::new(vec![0xd2, 0xfe, 0x28]);
+
+        assert_eq!(Ok(0b110), bit_stream.next_bits(3));
+        assert_eq!(Ok(0b100), bit_stream.next_bits(3));
+        assert_eq!(Ok(0b10111), bit_stream.next_bits(5));
+        assert_eq!(Ok(0b11110), bit_stream.next_bits(5));
+        assert_eq!(Ok(0b00101), bit_stream.next_bits(5));
+        assert_eq!(3, bit_stream.remaining());
+
+        assert_eq!(
+            Err(DecodeError {
+                offset: 21,
+                reason: DecodeReason::Truncated
+            }),
+            bit_stream.next_bits(4)
+        );
+    }
+
+    #[test]
+    fn test_literal_from_bit_stream() {
+        assert_eq!(
+            Packet::Literal {
+                header: Header {
+                    version: 6,
+                    type_id: 4,
+                },
+                value: 2021
+            },
+            Packet::from_hex("D2FE28").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_packet_from_bad_hex() {
+        assert_eq!(
+            Err(ParseError::new(16, 1, 4, "a hexadecimal digit")),
+            Packet::from_hex("D2FX28")
+        );
+
+        assert_eq!(
+            Err(ParseError::new(16, 1, 6, "an even number of hexadecimal digits")),
+            Packet::from_hex("D2FE2")
+        );
+    }
+
+    #[test]
+    fn test_operator_from_bit_count() {
+        let expected = Packet::Operator {
+            header: Header {
+                version: 1,
+                type_id: 6,
+            },
+            sub_packets: vec![
+                Literal {
+                    header: Header {
+                        version: 6,
+                        type_id: 4,
+                    },
+                    value: 10,
+                },
+                Literal {
+                    header: Header {
+                        version: 2,
+                        type_id: 4,
+                    },
+                    value: 20,
+                },
+            ],
+        };
+
+        assert_eq!(expected, Packet::from_hex("38006F45291200").unwrap());
+    }
+
+    #[test]
+    fn test_operator_from_packet_count() {
+        let expected = Packet::Operator {
+            header: Header {
+                version: 7,
+                type_id: 3,
+            },
+            sub_packets: vec![
+                Literal {
+                    header: Header {
+                        version: 2,
+                        type_id: 4,
+                    },
+                    value: 1,
+                },
+                Literal {
+                    header: Header {
+                        version: 4,
+                        type_id: 4,
+                    },
+                    value: 2,
+                },
+                Literal {
+                    header: Header {
+                        version: 1,
+                        type_id: 4,
+                    },
+                    value: 3,
+                },
+            ],
+        };
+
+        assert_eq!(expected, Packet::from_hex("EE00D40C823060").unwrap());
+    }
+
+    #[test]
+    fn test_packet_version_sum() {
+        assert_eq!(16, Packet::from_hex("8A004A801A8002F478").unwrap().version_sum());
+        assert_eq!(12, Packet::from_hex("620080001611562C8802118E34").unwrap().version_sum());
+        assert_eq!(23, Packet::from_hex("C0015000016115A2E0802F182340").unwrap().version_sum());
+        assert_eq!(31, Packet::from_hex("A0016C880162017C3686B18A3D4780").unwrap().version_sum());
+    }
+
+    #[test]
+    fn test_eval() {
+        assert_eq!(3, Packet::from_hex("C200B40A82").unwrap().eval());
+        assert_eq!(54, Packet::from_hex("04005AC33890").unwrap().eval());
+        assert_eq!(7, Packet::from_hex("880086C3E88112").unwrap().eval());
+        assert_eq!(9, Packet::from_hex("CE00C43D881120").unwrap().eval());
+        assert_eq!(1, Packet::from_hex("D8005AC2A8F0").unwrap().eval());
+        assert_eq!(0, Packet::from_hex("F600BC2D8F").unwrap().eval());
+        assert_eq!(0, Packet::from_hex("9C005AC2F8F0").unwrap().eval());
+        assert_eq!(1, Packet::from_hex("9C0141080250320F1802104A08").unwrap().eval());
+    }
+
+    #[test]
+    fn test_display() {
+        let packet = Packet::from_hex("9C0141080250320F1802104A08").unwrap();
+
+        assert_eq!("eq(sum(1, 3), product(2, 2))", packet.to_string());
+        assert_eq!("eq@4(sum@2(1@2, 3@4), product@6(2@0, 2@2))", format!("{:#}", packet));
+
+        assert_eq!("2021", Packet::from_hex("D2FE28").unwrap().to_string());
+        assert_eq!("max(1, 2, 3)", Packet::from_hex("EE00D40C823060").unwrap().to_string());
+    }
+
+    #[test]
+    fn test_trace() {
+        let packet = Packet::from_hex("9C0141080250320F1802104A08").unwrap();
+
+        let expected = indoc! {"
+            eq(4, 4) = 1  [version 4]
+              sum(1, 3) = 4  [version 2]
+              product(2, 2) = 4  [version 6]
+            = 1"};
+
+        assert_eq!(expected, packet.trace().to_string());
+
+        let packet = Packet::from_hex("A0016C880162017C3686B18A3D4780").unwrap();
+        let trace = packet.trace();
+
+        assert_eq!(3, trace.entries.len());
+        assert_eq!(vec![6, 6, 12, 15, 15], trace.entries[2].operands);
+        assert_eq!(packet.eval(), trace.value);
+
+        assert_eq!("= 2021", Packet::from_hex("D2FE28").unwrap().trace().to_string());
+    }
+
+    #[test]
+    fn test_checked_eval() {
+        assert_eq!(Ok(1), Packet::from_hex("9C0141080250320F1802104A08").unwrap().checked_eval());
+
+        // sum(1, product(2^64 - 1, 2))
+        let packet = Operator {
+            header: Header::new(0, 0),
+            sub_packets: vec![
+                literal(1),
+                Operator {
+                    header: Header::new(0, 1),
+                    sub_packets: vec![literal(u64::MAX), literal(2)],
+                },
+            ],
+        };
+
+        let overflow = packet.checked_eval().unwrap_err();
+
+        assert_eq!(vec![1], overflow.path);
+        assert_eq!("Value overflows 64 bits at packet root[1]", overflow.to_string());
+        assert_eq!(
+            "product(18446744073709551615, 2)",
+            packet.at_path(&overflow.path).unwrap().to_string()
+        );
+
+        assert_eq!("36893488147419103231", packet.eval_big().to_string());
+        assert_eq!(None, packet.at_path(&[1, 0, 0]));
+    }
+
+    #[test]
+    fn test_big_literal() {
+        // A literal with 20 nibble groups of 1 (a `u64` only holds 16)
+        let value = BigUint::from_nibbles(&[1; 20]);
+        let packet = Operator {
+            header: Header::new(0, 3),
+            sub_packets: vec![
+                literal(7),
+                BigLiteral {
+                    header: Header::new(0, Packet::LITERAL_TYPE_ID),
+                    value: value.clone(),
+                },
+            ],
+        };
+
+        let decoded = Packet::from_hex(&packet.to_hex(None).unwrap()).unwrap();
+
+        assert_eq!(packet, decoded);
+        assert_eq!("max(7, 80595054640975278313745)", decoded.to_string());
+        assert_eq!(value, decoded.eval_big());
+        assert_eq!(vec![1], decoded.checked_eval().unwrap_err().path);
+
+        // Leading zero nibble groups don't make a literal big
+        let mut bit_writer = BitWriter::new();
+        bit_writer.push_bits(Packet::LITERAL_TYPE_ID as u64, 6);
+
+        for _ in 0..19 {
+            bit_writer.push_bits(Packet::LITERAL_HAS_MORE_BIT as u64, 5);
+        }
+
+        bit_writer.push_bits(5, 5);
+
+        assert_eq!(
+            Ok(literal(5)),
+            Packet::decode(BitStream::new(bit_writer.into_bytes()))
+        );
+    }
+
+    fn literal(value: u64) -> Packet {
+        Literal {
+            header: Header::new(0, Packet::LITERAL_TYPE_ID),
+            value,
+        }
+    }
+
+    fn decode_error(offset: usize, reason: DecodeReason) -> DecodeError {
+        DecodeError { offset, reason }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(
+            Err(decode_error(16, DecodeReason::Truncated)),
+            Packet::decode(BitStream::from_hex("D2FE").unwrap())
+        );
+
+        assert_eq!(
+            Err(ParseError::new(16, 1, 5, "the rest of the packet")),
+            Packet::from_hex("D2FE")
+        );
+
+        // A gt of the literals 1, 2 and 3 (which `to_hex` refuses to encode)
+        let error = Packet::decode(BitStream::from_hex("1600C408821060").unwrap()).unwrap_err();
+
+        assert_eq!(
+            decode_error(
+                0,
+                DecodeReason::BadArity {
+                    type_id: 5,
+                    sub_packets: 3
+                }
+            ),
+            error
+        );
+        assert_eq!(
+            "Bit 0: gt has 3 sub-packets, but needs exactly two sub-packets",
+            error.to_string()
+        );
+
+        // A min with no sub-packets
+        assert_eq!(
+            Err(ParseError::new(16, 1, 1, "at least one sub-packet")),
+            Packet::from_hex("0A0000")
+        );
+
+        assert_eq!(
+            Err(decode_error(23, DecodeReason::NonzeroPadding)),
+            Packet::decode(BitStream::from_hex("D2FE29").unwrap())
+        );
+
+        // A sum that claims 10 bits of sub-packets, but holds an 11-bit literal
+        let mut bit_writer = BitWriter::new();
+        // Version 0, type ID 0 (sum), length type 0
+        bit_writer.push_bits(0, 7);
+        bit_writer.push_bits(10, 15);
+        literal(1).write_to(&mut bit_writer, None).unwrap();
+
+        assert_eq!(
+            Err(decode_error(
+                0,
+                DecodeReason::BitCountMismatch {
+                    declared: 10,
+                    actual: 11
+                }
+            )),
+            Packet::decode(BitStream::new(bit_writer.into_bytes()))
+        );
+    }
+
+    #[test]
+    fn test_decode_lenient() {
+        // A sum of 7, an == of just 1 and a max of nothing at all, followed by a one bit in its
+        // padding
+        let (decoded, problems) =
+            Packet::decode_lenient(BitStream::from_hex("0200C438F00220438008").unwrap());
+
+        assert_eq!(None, decoded);
+        assert_eq!(
+            vec![
+                decode_error(
+                    29,
+                    DecodeReason::BadArity {
+                        type_id: 7,
+                        sub_packets: 1
+                    }
+                ),
+                decode_error(
+                    58,
+                    DecodeReason::BadArity {
+                        type_id: 3,
+                        sub_packets: 0
+                    }
+                ),
+                decode_error(76, DecodeReason::NonzeroPadding),
+            ],
+            problems
+        );
+
+        // A gt with a single operand
+        assert_eq!(
+            (
+                None,
+                vec![decode_error(
+                    0,
+                    DecodeReason::BadArity {
+                        type_id: 5,
+                        sub_packets: 1
+                    }
+                )]
+            ),
+            Packet::decode_lenient(BitStream::from_hex("D6004408").unwrap())
+        );
+
+        // Stray padding bits don't stop the packet from being usable
+        assert_eq!(
+            (
+                Some(Packet::Literal {
+                    header: Header::new(6, 4),
+                    value: 2021
+                }),
+                vec![decode_error(23, DecodeReason::NonzeroPadding)]
+            ),
+            Packet::decode_lenient(BitStream::from_hex("D2FE29").unwrap())
+        );
+
+        assert_eq!(
+            (None, vec![decode_error(16, DecodeReason::Truncated)]),
+            Packet::decode_lenient(BitStream::from_hex("D2FE").unwrap())
+        );
+
+        assert_eq!(
+            (Some(literal(3)), vec![]),
+            Packet::decode_lenient(BitStream::from_hex(&literal(3).to_hex(None).unwrap()).unwrap())
+        );
+    }
+}
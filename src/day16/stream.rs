@@ -0,0 +1,472 @@
+use crate::day16::bignum::BigUint;
+use crate::day16::{DecodeError, DecodeReason, Header, Packet};
+use std::error;
+use std::fmt::{Display, Formatter};
+use std::io::{self, BufReader, Read};
+
+// How a reader spells out a transmission
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    // Hexadecimal digits, as in puzzle inputs; whitespace (like a trailing newline) is skipped
+    Hex,
+    // Raw bytes, most significant bit first
+    Binary,
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    // A byte (at the given index in the source) that isn't a hexadecimal digit
+    InvalidHexDigit(usize, u8),
+    Decode(DecodeError),
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamError::Io(error) => error.fmt(f),
+            StreamError::InvalidHexDigit(index, byte) => write!(
+                f,
+                "Byte {}: {:?} isn't a hexadecimal digit",
+                index,
+                *byte as char
+            ),
+            StreamError::Decode(error) => error.fmt(f),
+        }
+    }
+}
+
+impl error::Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(error: io::Error) -> Self {
+        StreamError::Io(error)
+    }
+}
+
+impl From<DecodeError> for StreamError {
+    fn from(error: DecodeError) -> Self {
+        StreamError::Decode(error)
+    }
+}
+
+// What a `StreamDecoder` finds as it reads, in the order the packets appear in the transmission.
+// Every `StartOperator` is matched by an `EndOperator` once all of its sub-packets have been read.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Event {
+    StartOperator(Header),
+    Literal(Header, u64),
+    BigLiteral(Header, BigUint),
+    EndOperator(Header),
+}
+
+// Like a `BitStream`, but pulls bits from a reader as it needs them rather than holding the whole
+// transmission
+struct StreamBits<R: Read> {
+    bytes: io::Bytes<BufReader<R>>,
+    encoding: Encoding,
+    bytes_read: usize,
+    // Bits we've read from the source but haven't handed out yet, in the lowest `buffered` bits
+    buffer: u8,
+    buffered: usize,
+    position: usize,
+}
+
+impl<R: Read> StreamBits<R> {
+    // Reads the next byte (or hex digit) from the source, returning its bits and how many there are
+    fn next_chunk(&mut self) -> Result<Option<(u8, usize)>, StreamError> {
+        for byte in self.bytes.by_ref() {
+            let byte = byte?;
+            let index = self.bytes_read;
+            self.bytes_read += 1;
+
+            match self.encoding {
+                Encoding::Binary => return Ok(Some((byte, 8))),
+                Encoding::Hex if byte.is_ascii_whitespace() => continue,
+                Encoding::Hex => {
+                    let digit = (byte as char)
+                        .to_digit(16)
+                        .ok_or(StreamError::InvalidHexDigit(index, byte))?;
+
+                    return Ok(Some((digit as u8, 4)));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Refills the buffer if it's empty, returning false if the source has run dry
+    fn fill(&mut self) -> Result<bool, StreamError> {
+        if self.buffered == 0 {
+            match self.next_chunk()? {
+                Some((bits, count)) => {
+                    self.buffer = bits;
+                    self.buffered = count;
+                }
+                None => return Ok(false),
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn next_bits(&mut self, n_bits: usize) -> Result<u32, StreamError> {
+        let offset = self.position;
+        let mut next_bits = 0u32;
+
+        for _ in 0..n_bits {
+            if !self.fill()? {
+                return Err(DecodeError {
+                    offset,
+                    reason: DecodeReason::Truncated,
+                }
+                .into());
+            }
+
+            self.buffered -= 1;
+            self.position += 1;
+
+            next_bits <<= 1;
+            next_bits |= ((self.buffer >> self.buffered) & 1) as u32;
+        }
+
+        Ok(next_bits)
+    }
+}
+
+// An operator whose sub-packets we're partway through
+struct OpenOperator {
+    header: Header,
+    offset: usize,
+    length: Length,
+    sub_packets: usize,
+}
+
+enum Length {
+    // The sub-packets start at `start` and take `declared` bits
+    Bits { start: usize, declared: usize },
+    Packets(usize),
+}
+
+// Decodes a transmission from a reader as a stream of `Event`s, holding on to nothing but the
+// operators that are still open. Decoding stops at the first problem, which the iterator yields as
+// its last item; padding after the outermost packet must be all zeroes, as with `Packet::decode`.
+pub struct StreamDecoder<R: Read> {
+    bits: StreamBits<R>,
+    open: Vec<OpenOperator>,
+    started: bool,
+    finished: bool,
+}
+
+impl<R: Read> StreamDecoder<R> {
+    pub fn new(reader: R, encoding: Encoding) -> Self {
+        StreamDecoder {
+            bits: StreamBits {
+                bytes: BufReader::new(reader).bytes(),
+                encoding,
+                bytes_read: 0,
+                buffer: 0,
+                buffered: 0,
+                position: 0,
+            },
+            open: Vec::new(),
+            started: false,
+            finished: false,
+        }
+    }
+
+    // The offset (in bits) of the next bit to be read
+    pub fn position(&self) -> usize {
+        self.bits.position
+    }
+
+    pub fn next_event(&mut self) -> Result<Option<Event>, StreamError> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let result = self.read_event();
+
+        if !matches!(result, Ok(Some(_))) {
+            self.finished = true;
+        }
+
+        result
+    }
+
+    fn read_event(&mut self) -> Result<Option<Event>, StreamError> {
+        if let Some(operator) = self.open.last() {
+            let complete = match operator.length {
+                Length::Bits { start, declared } => self.bits.position >= start + declared,
+                Length::Packets(count) => operator.sub_packets == count,
+            };
+
+            if complete {
+                return self.close_operator().map(Some);
+            }
+        } else if self.started {
+            self.padding()?;
+            return Ok(None);
+        }
+
+        self.started = true;
+
+        if let Some(parent) = self.open.last_mut() {
+            parent.sub_packets += 1;
+        }
+
+        let offset = self.bits.position;
+        let header = Header::new(self.bits.next_bits(3)? as u8, self.bits.next_bits(3)? as u8);
+
+        if header.type_id == Packet::LITERAL_TYPE_ID {
+            let mut nibbles = Vec::new();
+
+            loop {
+                let next_nibble = self.bits.next_bits(5)? as u8;
+                nibbles.push(next_nibble & Packet::LITERAL_NIBBLE_MASK);
+
+                if next_nibble & Packet::LITERAL_HAS_MORE_BIT == 0 {
+                    break;
+                }
+            }
+
+            let value = BigUint::from_nibbles(&nibbles);
+
+            return Ok(Some(match value.to_u64() {
+                Some(value) => Event::Literal(header, value),
+                None => Event::BigLiteral(header, value),
+            }));
+        }
+
+        let length = if self.bits.next_bits(1)? == 0 {
+            let declared = self.bits.next_bits(15)? as usize;
+
+            Length::Bits {
+                start: self.bits.position,
+                declared,
+            }
+        } else {
+            Length::Packets(self.bits.next_bits(11)? as usize)
+        };
+
+        self.open.push(OpenOperator {
+            header,
+            offset,
+            length,
+            sub_packets: 0,
+        });
+
+        Ok(Some(Event::StartOperator(header)))
+    }
+
+    fn close_operator(&mut self) -> Result<Event, StreamError> {
+        let operator = self.open.pop().unwrap();
+
+        if let Length::Bits { start, declared } = operator.length {
+            let actual = self.bits.position - start;
+
+            if actual != declared {
+                return Err(DecodeError {
+                    offset: operator.offset,
+                    reason: DecodeReason::BitCountMismatch { declared, actual },
+                }
+                .into());
+            }
+        }
+
+        if let Some(reason) =
+            DecodeReason::check_operator(operator.header.type_id, operator.sub_packets)
+        {
+            return Err(DecodeError {
+                offset: operator.offset,
+                reason,
+            }
+            .into());
+        }
+
+        Ok(Event::EndOperator(operator.header))
+    }
+
+    fn padding(&mut self) -> Result<(), StreamError> {
+        while self.bits.fill()? {
+            let offset = self.bits.position;
+
+            if self.bits.next_bits(1)? != 0 {
+                return Err(DecodeError {
+                    offset,
+                    reason: DecodeReason::NonzeroPadding,
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    // Evaluates the transmission as `Packet::eval` would, keeping only a running value for each
+    // open sum, product, min or max rather than all of its operands
+    pub fn eval(self) -> Result<u64, StreamError> {
+        let mut stack: Vec<(u8, Vec<u64>)> = vec![(Packet::LITERAL_TYPE_ID, Vec::new())];
+
+        for event in self {
+            let value = match event? {
+                Event::StartOperator(header) => {
+                    stack.push((header.type_id, Vec::new()));
+                    continue;
+                }
+                Event::Literal(_, value) => value,
+                Event::BigLiteral(_, value) => value.low_u64(),
+                Event::EndOperator(header) => {
+                    let (_, operands) = stack.pop().unwrap();
+                    Packet::apply(header.type_id, &operands)
+                }
+            };
+
+            let (type_id, operands) = stack.last_mut().unwrap();
+            operands.push(value);
+
+            if *type_id <= 3 && operands.len() == 2 {
+                let value = Packet::apply(*type_id, operands);
+                *operands = vec![value];
+            }
+        }
+
+        Ok(stack[0].1[0])
+    }
+
+    pub fn version_sum(self) -> Result<u32, StreamError> {
+        let mut sum = 0;
+
+        for event in self {
+            match event? {
+                Event::StartOperator(header)
+                | Event::Literal(header, _)
+                | Event::BigLiteral(header, _) => sum += header.version as u32,
+                Event::EndOperator(_) => {}
+            }
+        }
+
+        Ok(sum)
+    }
+}
+
+impl<R: Read> Iterator for StreamDecoder<R> {
+    type Item = Result<Event, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_event().transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day16::BitStream;
+
+    fn hex_decoder(hex: &str) -> StreamDecoder<&[u8]> {
+        StreamDecoder::new(hex.as_bytes(), Encoding::Hex)
+    }
+
+    #[test]
+    fn test_events() {
+        let events: Vec<Event> = hex_decoder("9C0141080250320F1802104A08\n")
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                Event::StartOperator(Header::new(4, 7)),
+                Event::StartOperator(Header::new(2, 0)),
+                Event::Literal(Header::new(2, 4), 1),
+                Event::Literal(Header::new(4, 4), 3),
+                Event::EndOperator(Header::new(2, 0)),
+                Event::StartOperator(Header::new(6, 1)),
+                Event::Literal(Header::new(0, 4), 2),
+                Event::Literal(Header::new(2, 4), 2),
+                Event::EndOperator(Header::new(6, 1)),
+                Event::EndOperator(Header::new(4, 7)),
+            ],
+            events
+        );
+
+        let mut decoder = hex_decoder("D2FE28");
+        assert_eq!(Some(Event::Literal(Header::new(6, 4), 2021)), decoder.next_event().unwrap());
+        assert_eq!(21, decoder.position());
+        assert_eq!(None, decoder.next_event().unwrap());
+    }
+
+    #[test]
+    fn test_eval() {
+        for hex in [
+            "C200B40A82",
+            "04005AC33890",
+            "880086C3E88112",
+            "CE00C43D881120",
+            "D8005AC2A8F0",
+            "F600BC2D8F",
+            "9C005AC2F8F0",
+            "9C0141080250320F1802104A08",
+            "A0016C880162017C3686B18A3D4780",
+        ] {
+            let packet = Packet::from_hex(hex).unwrap();
+            let bytes = hex::decode(hex).unwrap();
+
+            assert_eq!(packet.eval(), hex_decoder(hex).eval().unwrap());
+            assert_eq!(packet.version_sum(), hex_decoder(hex).version_sum().unwrap());
+            assert_eq!(
+                packet.eval(),
+                StreamDecoder::new(bytes.as_slice(), Encoding::Binary).eval().unwrap()
+            );
+        }
+
+        // A sum of many more literals than we'd like to hold at once
+        let packet = Packet::Operator {
+            header: Header::new(0, 0),
+            sub_packets: (0..2000)
+                .map(|value| Packet::Literal {
+                    header: Header::new(0, Packet::LITERAL_TYPE_ID),
+                    value,
+                })
+                .collect(),
+        };
+
+        let hex = packet.to_hex(None).unwrap();
+        assert_eq!(1999 * 1000, hex_decoder(&hex).eval().unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        let decode_error = |hex: &str| match hex_decoder(hex).last() {
+            Some(Err(StreamError::Decode(error))) => Some(error),
+            _ => None,
+        };
+
+        assert_eq!(
+            Packet::decode(BitStream::from_hex("D2FE").unwrap()).err(),
+            decode_error("D2FE")
+        );
+        assert_eq!(
+            Packet::decode(BitStream::from_hex("D2FE29").unwrap()).err(),
+            decode_error("D2FE29")
+        );
+
+        // An operator with a bit count (27, down to 26) that ends partway through its last
+        // sub-packet
+        let mut bytes = hex::decode("38006F45291200").unwrap();
+        bytes[2] -= 4;
+        let hex = hex::encode(&bytes);
+
+        assert_eq!(Packet::decode(BitStream::new(bytes)).err(), decode_error(&hex));
+
+        assert!(matches!(
+            hex_decoder("D2XE28").next(),
+            Some(Err(StreamError::InvalidHexDigit(2, b'X')))
+        ));
+
+        // Nothing more comes after an error
+        let mut decoder = hex_decoder("D2FE");
+        assert!(decoder.next().unwrap().is_err());
+        assert!(decoder.next().is_none());
+    }
+}
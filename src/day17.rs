@@ -1,24 +1,8 @@
+use crate::parse_error::ParseError;
+use crate::solution::{Input, Solution};
+use std::error;
 use std::ops::RangeInclusive;
 use std::str::FromStr;
-use std::{env, error};
-
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let target_area = TargetArea::from_str(std::fs::read_to_string(path)?.as_str()).unwrap();
-
-        println!("Max height: {}", target_area.max_height());
-        println!(
-            "Distinct trajectories: {}",
-            target_area.distinct_trajectories().len()
-        );
-
-        Ok(())
-    } else {
-        Err("Usage: day17 INPUT_FILE_PATH".into())
-    }
-}
 
 // OH BOY MATH I KNOW MATH. Okay! So for a target area, we can get a minimum x velocity that will
 // ever reach the target regardless of the y velocity. The farthest a projectile will ever go with
@@ -63,10 +47,10 @@ fn main() -> Result<(), Box<dyn error::Error>> {
 // - For every pair of x_candidate, y_candidate, figure out if they're in the target zone at
 //   overlapping times. If they are, then it's a viable trajectory.
 
-type Trajectory = (i32, i32);
+pub type Trajectory = (i32, i32);
 
 #[derive(Debug, Eq, PartialEq)]
-struct TargetArea {
+pub struct TargetArea {
     x_range: RangeInclusive<i32>,
     y_range: RangeInclusive<i32>,
 }
@@ -184,35 +168,59 @@ impl TargetArea {
 }
 
 impl FromStr for TargetArea {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let coordinates = string.strip_prefix("target area: ").unwrap();
-        let mut ranges = coordinates.split(", ");
-
-        let mut x_range = ranges
-            .next()
-            .unwrap()
-            .strip_prefix("x=")
-            .unwrap()
-            .split("..");
-        let x_min = i32::from_str(x_range.next().unwrap()).unwrap();
-        let x_max = i32::from_str(x_range.next().unwrap()).unwrap();
-
-        let mut y_range = ranges
-            .next()
-            .unwrap()
-            .strip_prefix("y=")
-            .unwrap()
-            .split("..");
-        let y_min = i32::from_str(y_range.next().unwrap()).unwrap();
-        let y_max = i32::from_str(y_range.next().unwrap()).unwrap();
+        let line = string.trim_end();
+
+        let coordinates = line
+            .strip_prefix("target area: ")
+            .ok_or_else(|| ParseError::at(17, line, line, "\"target area: \""))?;
+
+        let (x_range, y_range) = coordinates
+            .split_once(", ")
+            .ok_or_else(|| ParseError::at_end(17, line, "\", \" between ranges"))?;
 
         Ok(TargetArea {
-            x_range: x_min..=x_max,
-            y_range: y_min..=y_max,
+            x_range: parse_range(line, x_range, "x=")?,
+            y_range: parse_range(line, y_range, "y=")?,
+        })
+    }
+}
+
+fn parse_range(line: &str, range: &str, prefix: &str) -> Result<RangeInclusive<i32>, ParseError> {
+    let bounds = range
+        .strip_prefix(prefix)
+        .ok_or_else(|| ParseError::at(17, line, range, format!("\"{}\"", prefix)))?;
+
+    let (min, max) = bounds
+        .split_once("..")
+        .ok_or_else(|| ParseError::at(17, line, bounds, "\"..\" between bounds"))?;
+
+    let min = i32::from_str(min).map_err(|_| ParseError::at(17, line, min, "a lower bound"))?;
+    let max = i32::from_str(max).map_err(|_| ParseError::at(17, line, max, "an upper bound"))?;
+
+    Ok(min..=max)
+}
+
+pub struct Day17 {
+    target_area: TargetArea,
+}
+
+impl Solution for Day17 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day17 {
+            target_area: TargetArea::from_str(input.text()?)?,
         })
     }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.target_area.max_height().to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.target_area.distinct_trajectories().len().to_string())
+    }
 }
 
 #[cfg(test)]
@@ -230,6 +238,16 @@ mod test {
             expected,
             TargetArea::from_str("target area: x=20..30, y=-10..-5").unwrap()
         );
+
+        assert_eq!(
+            Err(ParseError::new(17, 1, 31, "an upper bound")),
+            TargetArea::from_str("target area: x=20..30, y=-10..")
+        );
+
+        assert_eq!(
+            Err(ParseError::new(17, 1, 1, "\"target area: \"")),
+            TargetArea::from_str("x=20..30, y=-10..-5")
+        );
     }
 
     #[test]
@@ -0,0 +1,283 @@
+use crate::day18::{Element, ReductionAction, SnailfishNumber};
+use std::error;
+use std::fmt::{Display, Formatter};
+use std::iter::Sum;
+use std::ops::Add;
+use std::str::FromStr;
+
+// A snailfish number as its literals from left to right, each with the number of pairs it's
+// nested inside. Pairs are implied: two neighboring literals at the same depth don't always make
+// a pair, but the leftmost two (at or past any given depth) do, since anything to the left of two
+// that don't would hold a deeper pair of its own.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlatSnailfishNumber {
+    elements: Vec<(u32, usize)>,
+}
+
+impl FlatSnailfishNumber {
+    // Literals nested inside this many pairs (so pairs nested inside four) explode
+    const EXPLODE_DEPTH: usize = 5;
+
+    pub fn elements(&self) -> &[(u32, usize)] {
+        &self.elements
+    }
+
+    pub fn magnitude(&self) -> u32 {
+        // Fold pairs together as soon as both of their halves are on the stack
+        let mut stack: Vec<(u32, usize)> = Vec::with_capacity(self.elements.len());
+
+        for &element in &self.elements {
+            stack.push(element);
+
+            while stack.len() >= 2 && stack[stack.len() - 1].1 == stack[stack.len() - 2].1 {
+                let (right, depth) = stack.pop().unwrap();
+                let (left, _) = stack.pop().unwrap();
+
+                stack.push((3 * left + 2 * right, depth - 1));
+            }
+        }
+
+        stack[0].0
+    }
+
+    fn add_ref(&self, rhs: &Self) -> Self {
+        let mut elements = Vec::with_capacity(self.elements.len() + rhs.elements.len());
+
+        elements.extend(
+            self.elements
+                .iter()
+                .chain(rhs.elements.iter())
+                .map(|&(value, depth)| (value, depth + 1)),
+        );
+
+        let mut sum = FlatSnailfishNumber { elements };
+        sum.reduce();
+
+        sum
+    }
+
+    fn reduce(&mut self) {
+        while self.reduce_step().is_some() {}
+    }
+
+    // Takes the same steps as `SnailfishNumber::reduce_step`
+    fn reduce_step(&mut self) -> Option<ReductionAction> {
+        self.try_explode().or_else(|| self.try_split())
+    }
+
+    fn try_explode(&mut self) -> Option<ReductionAction> {
+        // Like `SnailfishNumber::try_explode`, explode the leftmost pair of literals that's nested
+        // too deeply; the leftmost deep literal isn't always half of one, since an unreduced sum
+        // can nest pairs more deeply than that
+        let i = self.elements.windows(2).position(|pair| {
+            pair[0].1 >= FlatSnailfishNumber::EXPLODE_DEPTH && pair[0].1 == pair[1].1
+        })?;
+
+        let (left, depth) = self.elements[i];
+        let (right, _) = self.elements[i + 1];
+
+        if i > 0 {
+            self.elements[i - 1].0 += left;
+        }
+
+        if let Some((value, _)) = self.elements.get_mut(i + 2) {
+            *value += right;
+        }
+
+        self.elements.splice(i..i + 2, [(0, depth - 1)]);
+
+        Some(ReductionAction::Explode {
+            left,
+            right,
+            depth: depth - 1,
+        })
+    }
+
+    fn try_split(&mut self) -> Option<ReductionAction> {
+        let i = self.elements.iter().position(|&(value, _)| value >= 10)?;
+        let (value, depth) = self.elements[i];
+
+        self.elements
+            .splice(i..i + 1, [(value / 2, depth + 1), (value / 2 + value % 2, depth + 1)]);
+
+        Some(ReductionAction::Split(value))
+    }
+
+    fn flatten(element: &Element, depth: usize, elements: &mut Vec<(u32, usize)>) {
+        match element {
+            Element::Literal(value) => elements.push((*value, depth)),
+            Element::Pair(pair) => {
+                FlatSnailfishNumber::flatten(&pair.left, depth + 1, elements);
+                FlatSnailfishNumber::flatten(&pair.right, depth + 1, elements);
+            }
+        }
+    }
+
+    // Rebuilds the element starting at `next`, which sits inside `depth` pairs
+    fn unflatten(&self, next: &mut usize, depth: usize) -> Element {
+        if self.elements[*next].1 == depth {
+            *next += 1;
+            Element::Literal(self.elements[*next - 1].0)
+        } else {
+            let left = self.unflatten(next, depth + 1);
+            let right = self.unflatten(next, depth + 1);
+
+            Element::Pair(Box::new(SnailfishNumber { left, right }))
+        }
+    }
+}
+
+impl From<&SnailfishNumber> for FlatSnailfishNumber {
+    fn from(number: &SnailfishNumber) -> Self {
+        let mut elements = Vec::new();
+
+        FlatSnailfishNumber::flatten(&number.left, 1, &mut elements);
+        FlatSnailfishNumber::flatten(&number.right, 1, &mut elements);
+
+        FlatSnailfishNumber { elements }
+    }
+}
+
+impl From<&FlatSnailfishNumber> for SnailfishNumber {
+    fn from(number: &FlatSnailfishNumber) -> Self {
+        let mut next = 0;
+
+        SnailfishNumber {
+            left: number.unflatten(&mut next, 1),
+            right: number.unflatten(&mut next, 1),
+        }
+    }
+}
+
+impl Add for FlatSnailfishNumber {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add_ref(&rhs)
+    }
+}
+
+impl Sum for FlatSnailfishNumber {
+    fn sum<I: Iterator<Item=Self>>(mut iter: I) -> Self {
+        let mut sum = iter.next().unwrap();
+
+        for next in iter {
+            sum = sum + next;
+        }
+
+        sum
+    }
+}
+
+impl Display for FlatSnailfishNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        SnailfishNumber::from(self).fmt(f)
+    }
+}
+
+impl FromStr for FlatSnailfishNumber {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Ok(FlatSnailfishNumber::from(&SnailfishNumber::from_str(string)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    const HOMEWORK: &str = indoc! {"
+        [[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+        [[[5,[2,8]],4],[5,[[9,9],0]]]
+        [6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+        [[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+        [[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+        [[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+        [[[[5,4],[7,7]],8],[[8,3],8]]
+        [[9,3],[[9,9],[6,[4,9]]]]
+        [[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+        [[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]
+    "};
+
+    fn homework() -> Vec<SnailfishNumber> {
+        HOMEWORK
+            .lines()
+            .map(|line| SnailfishNumber::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_conversion() {
+        let number = SnailfishNumber::from_str("[[[[[9,8],1],2],3],4]").unwrap();
+        let flat = FlatSnailfishNumber::from(&number);
+
+        assert_eq!(&[(9, 5), (8, 5), (1, 4), (2, 3), (3, 2), (4, 1)], flat.elements());
+        assert_eq!(number, SnailfishNumber::from(&flat));
+        assert_eq!("[[[[[9,8],1],2],3],4]", flat.to_string());
+
+        for number in homework() {
+            assert_eq!(number, SnailfishNumber::from(&FlatSnailfishNumber::from(&number)));
+        }
+    }
+
+    #[test]
+    fn test_reduction() {
+        // Every step of every addition matches the tree version
+        let numbers = homework();
+
+        for lhs in &numbers {
+            for rhs in &numbers {
+                let sum = lhs.clone().add_unreduced(rhs.clone());
+                let mut flat = FlatSnailfishNumber::from(&sum);
+
+                for step in sum.reduction() {
+                    assert_eq!(Some(step.action), flat.reduce_step());
+                    assert_eq!(step.number, SnailfishNumber::from(&flat));
+                }
+
+                assert_eq!(None, flat.reduce_step());
+            }
+        }
+
+        // A sum with a pair nested inside five others, next to a literal nested just as deeply
+        let lhs = SnailfishNumber::from_str("[[[[1,[2,3]],0],0],0]").unwrap();
+        let sum = lhs.add_unreduced(SnailfishNumber::from_str("[1,1]").unwrap());
+        let mut flat = FlatSnailfishNumber::from(&sum);
+
+        for step in sum.reduction() {
+            assert_eq!(Some(step.action), flat.reduce_step());
+            assert_eq!(step.number, SnailfishNumber::from(&flat));
+        }
+
+        assert_eq!("[[[[0,3],0],0],[1,1]]", flat.to_string());
+        assert_eq!(172, flat.magnitude());
+    }
+
+    #[test]
+    fn test_add() {
+        let lhs = FlatSnailfishNumber::from_str("[[[[4,3],4],4],[7,[[8,4],9]]]").unwrap();
+        let rhs = FlatSnailfishNumber::from_str("[1,1]").unwrap();
+
+        assert_eq!("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", (lhs + rhs).to_string());
+
+        let numbers = homework();
+        let tree_sum: SnailfishNumber = numbers.iter().cloned().sum();
+        let flat_sum: FlatSnailfishNumber = numbers.iter().map(FlatSnailfishNumber::from).sum();
+
+        assert_eq!(tree_sum, SnailfishNumber::from(&flat_sum));
+        assert_eq!(4140, flat_sum.magnitude());
+    }
+
+    #[test]
+    fn test_magnitude() {
+        for (string, expected) in [
+            ("[[1,2],[[3,4],5]]", 143),
+            ("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", 1384),
+            ("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]", 3488),
+        ] {
+            assert_eq!(expected, FlatSnailfishNumber::from_str(string).unwrap().magnitude());
+        }
+    }
+}
@@ -1,44 +1,22 @@
-use std::{env, error, io};
-use std::cmp::max;
+use crate::solution::{Input, Solution};
+use std::error;
+use std::cmp::{max, Reverse};
 use std::collections::VecDeque;
 use std::fmt::{Display, Formatter};
-use std::fs::File;
-use std::io::BufRead;
 use std::iter::Sum;
 use std::ops::Add;
 use std::str::FromStr;
-use crate::Token::{ClosePair, Literal, OpenPair, Separator};
+use std::thread;
+use self::Token::{ClosePair, Literal, OpenPair, Separator};
+use self::flat::FlatSnailfishNumber;
+pub mod flat;
 
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let magnitude_of_sum = io::BufReader::new(File::open(path)?).lines()
-            .filter_map(|line| line.ok())
-            .map(|line| SnailfishNumber::from_str(line.as_str()).unwrap())
-            .sum::<SnailfishNumber>()
-            .magnitude();
-
-        println!("Magnitude of sum: {}", magnitude_of_sum);
-
-        let numbers: Vec<SnailfishNumber> = io::BufReader::new(File::open(path)?).lines()
-            .filter_map(|line| line.ok())
-            .map(|line| SnailfishNumber::from_str(line.as_str()).unwrap())
-            .collect();
-
-        println!("Largest possible magnitude of any sum: {}", largest_pair_magnitude(&numbers));
-
-        Ok(())
-    } else {
-        Err("Usage: day18 INPUT_FILE_PATH".into())
-    }
-}
-
-fn largest_pair_magnitude(numbers: &[SnailfishNumber]) -> u32 {
+// Finds the largest magnitude of any sum of two different numbers, or 0 if there aren't two
+pub fn largest_pair_magnitude(numbers: &[SnailfishNumber]) -> u32 {
     let mut max_magnitude = 0;
 
-    for i in 0..numbers.len() - 1 {
-        for j in i..numbers.len() {
+    for i in 0..numbers.len() {
+        for j in i + 1..numbers.len() {
             max_magnitude = max(max_magnitude, (numbers[i].clone() + numbers[j].clone()).magnitude());
             max_magnitude = max(max_magnitude, (numbers[j].clone() + numbers[i].clone()).magnitude());
         }
@@ -47,14 +25,65 @@ fn largest_pair_magnitude(numbers: &[SnailfishNumber]) -> u32 {
     max_magnitude
 }
 
+// The sum of two different numbers (by their indices) with the largest magnitude
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct LargestPair {
+    pub left: usize,
+    pub right: usize,
+    pub magnitude: u32,
+}
+
+// Searches every ordered pair of different numbers for the largest magnitude, splitting pairs
+// evenly across the given number of threads. Ties go to the pair that comes first, so the answer
+// doesn't depend on the number of threads.
+pub fn largest_pair(numbers: &[SnailfishNumber], threads: usize) -> Option<LargestPair> {
+    let numbers: Vec<FlatSnailfishNumber> = numbers.iter().map(FlatSnailfishNumber::from).collect();
+    let n = numbers.len();
+    let threads = threads.clamp(1, (n * n).max(1));
+
+    let best = |a: Option<LargestPair>, b: Option<LargestPair>| match (a, b) {
+        (Some(a), Some(b)) => {
+            let key = |pair: &LargestPair| (pair.magnitude, Reverse((pair.left, pair.right)));
+            Some(if key(&b) > key(&a) { b } else { a })
+        }
+        (a, b) => a.or(b),
+    };
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|worker| {
+                let numbers = &numbers;
+
+                scope.spawn(move || {
+                    (worker..n * n)
+                        .step_by(threads)
+                        .map(|k| (k / n, k % n))
+                        .filter(|(left, right)| left != right)
+                        .map(|(left, right)| LargestPair {
+                            left,
+                            right,
+                            magnitude: (numbers[left].clone() + numbers[right].clone()).magnitude(),
+                        })
+                        .fold(None, |largest, pair| best(largest, Some(pair)))
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap())
+            .fold(None, best)
+    })
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
-enum Element {
+pub enum Element {
     Pair(Box<SnailfishNumber>),
     Literal(u32),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-struct SnailfishNumber {
+pub struct SnailfishNumber {
     left: Element,
     right: Element,
 }
@@ -63,6 +92,56 @@ struct ExplodeState {
     zeroed_element: bool,
     left: Option<u32>,
     right: Option<u32>,
+    action: ReductionAction,
+}
+
+// A single step in reducing a snailfish number
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ReductionAction {
+    // The pair of literals that exploded, and how many pairs it was nested inside
+    Explode { left: u32, right: u32, depth: usize },
+    // The literal that split into a pair
+    Split(u32),
+}
+
+impl Display for ReductionAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReductionAction::Explode { left, right, depth } => {
+                write!(f, "explode [{},{}] at depth {}", left, right, depth)
+            }
+            ReductionAction::Split(value) => write!(f, "split {}", value),
+        }
+    }
+}
+
+// A reduction step along with the number it left behind
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReductionStep {
+    pub action: ReductionAction,
+    pub number: SnailfishNumber,
+}
+
+impl Display for ReductionStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "after {}: {}", self.action, self.number)
+    }
+}
+
+// Reduces a snailfish number one step at a time, yielding each intermediate number
+pub struct Reduction {
+    number: SnailfishNumber,
+}
+
+impl Iterator for Reduction {
+    type Item = ReductionStep;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.number.reduce_step().map(|action| ReductionStep {
+            action,
+            number: self.number.clone(),
+        })
+    }
 }
 
 impl SnailfishNumber {
@@ -106,6 +185,18 @@ impl SnailfishNumber {
         }
     }
 
+    // How many pairs the most deeply nested pair sits inside
+    fn nesting(&self) -> usize {
+        [&self.left, &self.right]
+            .iter()
+            .map(|element| match element {
+                Element::Literal(_) => 0,
+                Element::Pair(pair) => pair.nesting() + 1,
+            })
+            .max()
+            .unwrap()
+    }
+
     pub fn magnitude(&self) -> u32 {
         let left_magnitude = 3 * match &self.left {
             Element::Literal(value) => *value,
@@ -120,20 +211,37 @@ impl SnailfishNumber {
         left_magnitude + right_magnitude
     }
 
-    fn reduce(&mut self) {
-        loop {
-            if self.try_explode(0).is_some() {
-                continue;
-            } else if self.try_split() {
-                continue;
-            } else {
-                break;
-            }
+    // Puts two numbers in a pair without reducing the result, as in the first step of addition
+    pub fn add_unreduced(self, rhs: Self) -> Self {
+        SnailfishNumber {
+            left: Element::Pair(Box::new(self)),
+            right: Element::Pair(Box::new(rhs)),
         }
     }
 
+    // Steps through reducing this number; `a.add_unreduced(b).reduction()` shows how an addition
+    // plays out
+    pub fn reduction(self) -> Reduction {
+        Reduction { number: self }
+    }
+
+    fn reduce(&mut self) {
+        while self.reduce_step().is_some() {}
+    }
+
+    fn reduce_step(&mut self) -> Option<ReductionAction> {
+        self.try_explode(0)
+            .map(|explode_state| explode_state.action)
+            .or_else(|| self.try_split())
+    }
+
     fn try_explode(&mut self, depth: usize) -> Option<ExplodeState> {
-        if depth < 4 {
+        // `FromStr` won't nest pairs any deeper than the puzzle does, but an unreduced sum might,
+        // in which case the leftmost pair of literals further down explodes
+        let is_pair_of_literals =
+            matches!((&self.left, &self.right), (Element::Literal(_), Element::Literal(_)));
+
+        if depth < 4 || !is_pair_of_literals {
             if let Element::Pair(pair) = &mut self.left {
                 if let Some(explode_state) = pair.try_explode(depth + 1) {
                     if !explode_state.zeroed_element {
@@ -151,6 +259,7 @@ impl SnailfishNumber {
                         zeroed_element: true,
                         left: explode_state.left,
                         right: None,
+                        action: explode_state.action,
                     })
                 }
             }
@@ -172,6 +281,7 @@ impl SnailfishNumber {
                         zeroed_element: true,
                         left: None,
                         right: explode_state.right,
+                        action: explode_state.action,
                     })
                 }
             }
@@ -192,6 +302,7 @@ impl SnailfishNumber {
                 zeroed_element: false,
                 left: Some(left),
                 right: Some(right),
+                action: ReductionAction::Explode { left, right, depth },
             })
         }
 
@@ -212,35 +323,40 @@ impl SnailfishNumber {
         };
     }
 
-    fn try_split(&mut self) -> bool {
+    // Splits the leftmost literal that's 10 or more, if there is one
+    fn try_split(&mut self) -> Option<ReductionAction> {
         let split_left = match &mut self.left {
             Element::Literal(value) => if *value >= 10 {
+                let split = ReductionAction::Split(*value);
+
                 self.left = Element::Pair(Box::new(SnailfishNumber {
                     left: Element::Literal(*value / 2),
                     right: Element::Literal((*value / 2) + (*value % 2)),
                 }));
 
-                true
+                Some(split)
             } else {
-                false
+                None
             },
             Element::Pair(pair) => pair.try_split()
         };
 
-        if split_left {
-            return true;
+        if split_left.is_some() {
+            return split_left;
         }
 
         match &mut self.right {
             Element::Literal(value) => if *value >= 10 {
+                let split = ReductionAction::Split(*value);
+
                 self.right = Element::Pair(Box::new(SnailfishNumber {
                     left: Element::Literal(*value / 2),
                     right: Element::Literal((*value / 2) + (*value % 2)),
                 }));
 
-                true
+                Some(split)
             } else {
-                false
+                None
             },
             Element::Pair(pair) => pair.try_split()
         }
@@ -251,10 +367,7 @@ impl Add for SnailfishNumber {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let mut sum = SnailfishNumber {
-            left: Element::Pair(Box::new(self)),
-            right: Element::Pair(Box::new(rhs)),
-        };
+        let mut sum = self.add_unreduced(rhs);
 
         sum.reduce();
 
@@ -326,7 +439,48 @@ impl FromStr for SnailfishNumber {
             });
         }
 
-        Ok(SnailfishNumber::from_tokens(&mut tokens)?)
+        let number = SnailfishNumber::from_tokens(&mut tokens)?;
+
+        if number.nesting() > 4 {
+            return Err("Pairs can't be nested inside more than four other pairs".into());
+        }
+
+        Ok(number)
+    }
+}
+
+pub struct Day18 {
+    numbers: Vec<SnailfishNumber>,
+}
+
+impl Solution for Day18 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day18 {
+            numbers: input
+                .text()?
+                .lines()
+                .map(SnailfishNumber::from_str)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self
+            .numbers
+            .iter()
+            .cloned()
+            .sum::<SnailfishNumber>()
+            .magnitude()
+            .to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+
+        Ok(largest_pair(&self.numbers, threads)
+            .ok_or("Need at least two numbers")?
+            .magnitude
+            .to_string())
     }
 }
 
@@ -355,6 +509,7 @@ mod test {
         };
 
         assert_eq!(expected, SnailfishNumber::from_str("[[[[[9,8],1],2],3],4]").unwrap());
+        assert!(SnailfishNumber::from_str("[[[[[[1,2],3],4],5],6],7]").is_err());
     }
 
     #[test]
@@ -401,7 +556,7 @@ mod test {
         let expected = SnailfishNumber::from_str("[[[[0,7],4],[[7,8],[0,13]]],[1,1]]").unwrap();
 
         let mut split = SnailfishNumber::from_str("[[[[0,7],4],[15,[0,13]]],[1,1]]").unwrap();
-        assert!(split.try_split());
+        assert_eq!(Some(ReductionAction::Split(15)), split.try_split());
 
         assert_eq!(expected, split);
     }
@@ -416,6 +571,37 @@ mod test {
         assert_eq!(expected, reduced);
     }
 
+    #[test]
+    fn test_reduction() {
+        let lhs = SnailfishNumber::from_str("[[[[4,3],4],4],[7,[[8,4],9]]]").unwrap();
+        let rhs = SnailfishNumber::from_str("[1,1]").unwrap();
+
+        let sum = lhs.add_unreduced(rhs);
+        assert_eq!("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]", sum.to_string());
+
+        let trace: Vec<String> = sum.reduction().map(|step| step.to_string()).collect();
+
+        let expected = indoc! {"
+            after explode [4,3] at depth 4: [[[[0,7],4],[7,[[8,4],9]]],[1,1]]
+            after explode [8,4] at depth 4: [[[[0,7],4],[15,[0,13]]],[1,1]]
+            after split 15: [[[[0,7],4],[[7,8],[0,13]]],[1,1]]
+            after split 13: [[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]
+            after explode [6,7] at depth 4: [[[[0,7],4],[[7,8],[6,0]]],[8,1]]"};
+
+        assert_eq!(expected, trace.join("\n"));
+
+        // Adding a number that's due to explode nests a pair deeper than any we can parse
+        let lhs = SnailfishNumber::from_str("[[[[[9,8],1],2],3],4]").unwrap();
+        let sum = lhs.add_unreduced(SnailfishNumber::from_str("[1,1]").unwrap());
+        let steps: Vec<ReductionStep> = sum.reduction().collect();
+
+        assert_eq!(
+            Some(ReductionAction::Explode { left: 9, right: 8, depth: 5 }),
+            steps.first().map(|step| step.action)
+        );
+        assert_eq!("[[[[5,0],9],4],[1,1]]", steps.last().unwrap().number.to_string());
+    }
+
     #[test]
     fn test_add() {
         {
@@ -487,5 +673,31 @@ mod test {
             .collect();
 
         assert_eq!(3993, largest_pair_magnitude(&numbers));
+
+        let expected = LargestPair {
+            left: 8,
+            right: 0,
+            magnitude: 3993,
+        };
+
+        for threads in [1, 2, 3, 8, 1000] {
+            assert_eq!(Some(expected), largest_pair(&numbers, threads));
+        }
+
+        assert_eq!(None, largest_pair(&numbers[..1], 4));
+        assert_eq!(None, largest_pair(&[], 4));
+
+        // Sums that nest pairs inside five others, next to literals nested just as deeply, still
+        // reduce the same way with flat numbers
+        let numbers: Vec<SnailfishNumber> = ["[[[[1,[2,3]],0],0],0]", "[1,1]"]
+            .iter()
+            .map(|line| SnailfishNumber::from_str(line).unwrap())
+            .collect();
+
+        let largest = largest_pair(&numbers, 2).unwrap();
+
+        assert_eq!(largest_pair_magnitude(&numbers), largest.magnitude);
+        assert_eq!(172, largest.magnitude);
+        assert_eq!(0, largest_pair_magnitude(&numbers[..1]));
     }
 }
@@ -1,44 +1,16 @@
-mod point_cloud;
-mod rotation;
-mod vector;
-
-use crate::point_cloud::PointCloud;
-use crate::rotation::{RotationMatrix, ORIENTATIONS};
-use crate::vector::Vector3d;
+use crate::solution::{Input, Solution};
+use std::error;
+pub mod point_cloud;
+pub mod rotation;
+pub mod vector;
+
+use crate::day19::point_cloud::PointCloud;
+use crate::day19::rotation::{RotationMatrix, ORIENTATIONS};
+use crate::day19::vector::Vector3d;
 use std::cmp::max;
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::BufRead;
-use std::{env, error, io};
-
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let point_clouds = point_cloud::from_lines(
-            io::BufReader::new(File::open(path)?)
-                .lines()
-                .map(|line| line.unwrap()),
-        )
-        .unwrap();
-
-        println!(
-            "Distinct beacons: {}",
-            distinct_beacons(&point_clouds).len()
-        );
-
-        println!(
-            "Max distance between sensors: {}",
-            max_sensor_distance(&point_clouds)
-        );
-
-        Ok(())
-    } else {
-        Err("Usage: day19 INPUT_FILE_PATH".into())
-    }
-}
 
-fn align_point_clouds(point_clouds: &[PointCloud]) -> Vec<(RotationMatrix, Vector3d)> {
+pub fn align_point_clouds(point_clouds: &[PointCloud]) -> Vec<(RotationMatrix, Vector3d)> {
     let mut transformations = vec![None; point_clouds.len()];
 
     // Treat the first point cloud as our origin in terms of both position and orientation
@@ -73,7 +45,7 @@ fn align_point_clouds(point_clouds: &[PointCloud]) -> Vec<(RotationMatrix, Vecto
         .collect()
 }
 
-fn distinct_beacons(point_clouds: &[PointCloud]) -> HashSet<Vector3d> {
+pub fn distinct_beacons(point_clouds: &[PointCloud]) -> HashSet<Vector3d> {
     let transformations = align_point_clouds(point_clouds);
 
     let mut distinct_beacons = HashSet::new();
@@ -85,7 +57,7 @@ fn distinct_beacons(point_clouds: &[PointCloud]) -> HashSet<Vector3d> {
     distinct_beacons
 }
 
-fn max_sensor_distance(point_clouds: &[PointCloud]) -> u32 {
+pub fn max_sensor_distance(point_clouds: &[PointCloud]) -> u32 {
     let positions: Vec<Vector3d> = align_point_clouds(point_clouds)
         .iter()
         .map(|(_, translation)| *translation)
@@ -102,6 +74,26 @@ fn max_sensor_distance(point_clouds: &[PointCloud]) -> u32 {
     max_distance
 }
 
+pub struct Day19 {
+    point_clouds: Vec<PointCloud>,
+}
+
+impl Solution for Day19 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day19 {
+            point_clouds: point_cloud::from_lines(input.text()?.lines().map(String::from))?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(distinct_beacons(&self.point_clouds).len().to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(max_sensor_distance(&self.point_clouds).to_string())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
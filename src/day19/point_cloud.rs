@@ -1,5 +1,5 @@
-use crate::rotation::{RotationMatrix, ORIENTATIONS};
-use crate::vector::Vector3d;
+use crate::day19::rotation::{RotationMatrix, ORIENTATIONS};
+use crate::day19::vector::Vector3d;
 use std::collections::HashSet;
 use std::str::FromStr;
 
@@ -97,9 +97,9 @@ impl PointCloud {
 
 #[cfg(test)]
 mod test {
-    use crate::point_cloud::{from_lines, PointCloud};
-    use crate::rotation::ORIENTATIONS;
-    use crate::vector::Vector3d;
+    use crate::day19::point_cloud::{from_lines, PointCloud};
+    use crate::day19::rotation::ORIENTATIONS;
+    use crate::day19::vector::Vector3d;
     use std::collections::HashSet;
 
     const TEST_SCANNER_STRING: &str = include_str!("test_points.txt");
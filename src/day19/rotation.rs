@@ -50,15 +50,7 @@ impl RotationMatrix {
     }
 
     pub fn apply(&self, vector: &[i32; 3]) -> [i32; 3] {
-        let mut rotated = [0; 3];
-
-        for i in 0..3 {
-            for j in 0..3 {
-                rotated[i] += self.matrix[i][j] * vector[j];
-            }
-        }
-
-        rotated
+        self.matrix.map(|row| row.iter().zip(vector).map(|(m, v)| m * v).sum())
     }
 }
 
@@ -80,7 +72,7 @@ const fn cos(half_pi: u32) -> i32 {
 
 #[cfg(test)]
 mod test {
-    use crate::rotation::{RotationMatrix, ORIENTATIONS};
+    use crate::day19::rotation::{RotationMatrix, ORIENTATIONS};
     use std::collections::HashSet;
 
     #[test]
@@ -1,4 +1,4 @@
-use crate::rotation::RotationMatrix;
+use crate::day19::rotation::RotationMatrix;
 use std::ops::{Add, Sub};
 use std::str::FromStr;
 
@@ -35,7 +35,7 @@ impl FromStr for Vector3d {
     fn from_str(line: &str) -> Result<Self, Self::Err> {
         let components: Vec<i32> = line
             .split(',')
-            .map(|component| i32::from_str(component))
+            .map(i32::from_str)
             .collect::<Result<Vec<i32>, _>>()?;
 
         Ok(Vector3d {
@@ -48,13 +48,9 @@ impl Add for Vector3d {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let mut components = [0; 3];
-
-        for i in 0..3 {
-            components[i] = self.components[i] + rhs.components[i];
+        Vector3d {
+            components: std::array::from_fn(|i| self.components[i] + rhs.components[i]),
         }
-
-        Vector3d { components }
     }
 }
 
@@ -62,20 +58,16 @@ impl Sub for Vector3d {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let mut components = [0; 3];
-
-        for i in 0..3 {
-            components[i] = self.components[i] - rhs.components[i];
+        Vector3d {
+            components: std::array::from_fn(|i| self.components[i] - rhs.components[i]),
         }
-
-        Vector3d { components }
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::rotation::ORIENTATIONS;
+    use crate::day19::rotation::ORIENTATIONS;
 
     #[test]
     fn test_from_string() {
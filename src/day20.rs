@@ -1,31 +1,11 @@
+use crate::grid::Grid;
+use crate::parse_error::ParseError;
+use crate::solution::{Input, Solution};
 use self::Pixel::{Dark, Light};
 use std::str::FromStr;
-use std::{env, error};
+use std::error;
 
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let (algorithm, image) =
-            algorithm_and_image_from_str(std::fs::read_to_string(path)?.as_str()).unwrap();
-
-        println!(
-            "Light pixels in enhanced image after 2 steps: {}",
-            image.enhance(&algorithm, 2).light_pixel_count()
-        );
-
-        println!(
-            "Light pixels in enhanced image after 50 steps: {}",
-            image.enhance(&algorithm, 50).light_pixel_count()
-        );
-
-        Ok(())
-    } else {
-        Err("Usage: day20 INPUT_FILE_PATH".into())
-    }
-}
-
-fn algorithm_and_image_from_str(
+pub fn algorithm_and_image_from_str(
     string: &str,
 ) -> Result<(EnhancementAlgorithm, Image), Box<dyn error::Error>> {
     let mut pieces = string.splitn(2, "\n\n");
@@ -37,12 +17,12 @@ fn algorithm_and_image_from_str(
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-enum Pixel {
+pub enum Pixel {
     Light,
     Dark,
 }
 
-struct EnhancementAlgorithm {
+pub struct EnhancementAlgorithm {
     rules: [Pixel; 512],
 }
 
@@ -74,11 +54,8 @@ impl FromStr for EnhancementAlgorithm {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-struct Image {
-    width: usize,
-    height: usize,
-
-    pixels: Vec<Pixel>,
+pub struct Image {
+    pixels: Grid<Pixel>,
     infinite_pixel: Pixel,
 }
 
@@ -94,20 +71,16 @@ impl Image {
     }
 
     fn next(&self, enhancement_algorithm: &EnhancementAlgorithm) -> Self {
-        let mut enhanced_pixels = vec![Dark; (self.width + 2) * (self.height + 2)];
-
-        let enhanced_width = self.width + 2;
-        let enhanced_height = self.height + 2;
-
-        for y in 0..enhanced_height {
-            for x in 0..enhanced_width {
-                // Note that x and y here correspond to positions in the enhanced image, which will
-                // be offset by 1 in each direction within this image (i.e. we're adding a border of
-                // 1 pixel the whole way around).
-                enhanced_pixels[(y * enhanced_width) + x] = enhancement_algorithm
-                    .pixel(self.enhancement_index(x as isize - 1, y as isize - 1));
-            }
-        }
+        // Note that x and y here correspond to positions in the enhanced image, which will be
+        // offset by 1 in each direction within this image (i.e. we're adding a border of 1 pixel
+        // the whole way around).
+        let enhanced_pixels = Grid::from_fn(
+            self.pixels.width() + 2,
+            self.pixels.height() + 2,
+            |(y, x)| {
+                enhancement_algorithm.pixel(self.enhancement_index(x as isize - 1, y as isize - 1))
+            },
+        );
 
         let enhanced_infinite_pixel = match self.infinite_pixel {
             Light => enhancement_algorithm.pixel(EnhancementAlgorithm::MAX),
@@ -115,8 +88,6 @@ impl Image {
         };
 
         Image {
-            width: enhanced_width,
-            height: enhanced_height,
             pixels: enhanced_pixels,
             infinite_pixel: enhanced_infinite_pixel,
         }
@@ -130,10 +101,13 @@ impl Image {
     }
 
     fn pixel(&self, x: isize, y: isize) -> Pixel {
-        if x < 0 || x as usize >= self.width || y < 0 || y as usize >= self.height {
+        if x < 0 || y < 0 {
             self.infinite_pixel
         } else {
-            self.pixels[(y as usize * self.width) + x as usize]
+            self.pixels
+                .get((y as usize, x as usize))
+                .copied()
+                .unwrap_or(self.infinite_pixel)
         }
     }
 
@@ -155,32 +129,48 @@ impl Image {
 }
 
 impl FromStr for Image {
-    type Err = Box<dyn error::Error>;
+    type Err = ParseError;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let pixels: Vec<Pixel> = string
-            .chars()
-            .filter_map(|c| match c {
-                '#' => Some(Light),
-                '.' => Some(Dark),
-                _ => None,
-            })
-            .collect();
+        let pixels = Grid::parse(20, string, "'#' or '.'", |c| match c {
+            '#' => Some(Light),
+            '.' => Some(Dark),
+            _ => None,
+        })?;
+
+        Ok(Image {
+            pixels,
+            infinite_pixel: Dark,
+        })
+    }
+}
 
-        let width = string.find('\n').unwrap();
+pub struct Day20 {
+    algorithm: EnhancementAlgorithm,
+    image: Image,
+}
 
-        if pixels.len() % width == 0 {
-            let height = pixels.len() / width;
+impl Solution for Day20 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        let (algorithm, image) = algorithm_and_image_from_str(input.text()?)?;
 
-            Ok(Image {
-                width,
-                height,
-                pixels,
-                infinite_pixel: Dark,
-            })
-        } else {
-            Err("Bad image string length".into())
-        }
+        Ok(Day20 { algorithm, image })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self
+            .image
+            .enhance(&self.algorithm, 2)
+            .light_pixel_count()
+            .to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self
+            .image
+            .enhance(&self.algorithm, 50)
+            .light_pixel_count()
+            .to_string())
     }
 }
 
@@ -225,13 +215,12 @@ mod test {
     fn test_image_from_string() {
         let image = Image::from_str(TEST_IMAGE_STRING).unwrap();
 
-        assert_eq!(5, image.width);
-        assert_eq!(5, image.height);
+        assert_eq!(5, image.pixels.width());
+        assert_eq!(5, image.pixels.height());
         assert_eq!(Dark, image.infinite_pixel);
 
-        assert!(image
-            .pixels
-            .starts_with(&[Light, Dark, Dark, Light, Dark, Light, Dark]));
+        assert_eq!(&[Light, Dark, Dark, Light, Dark], image.pixels.row(0));
+        assert_eq!(&[Light, Dark, Dark, Dark, Dark], image.pixels.row(1));
     }
 
     #[test]
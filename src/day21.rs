@@ -1,32 +1,9 @@
+use crate::solution::{Input, Solution};
+use std::error;
 use std::cmp::{max, min};
 use std::collections::VecDeque;
-use std::str::FromStr;
-use std::{env, error};
 
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() == 3 {
-        let p1_position = u32::from_str(args[1].as_str())?;
-        let p2_position = u32::from_str(args[2].as_str())?;
-
-        println!(
-            "Outcome with deterministic die: {}",
-            play_deterministic_game(p1_position, p2_position)
-        );
-
-        println!(
-            "Outcome with quantum die: {}",
-            play_quantum_game(p1_position, p2_position)
-        );
-
-        Ok(())
-    } else {
-        Err("Usage: day21 P1_START P2_START".into())
-    }
-}
-
-struct GameState {
+pub struct GameState {
     active_player: usize,
     positions: [u32; 2],
     scores: [u32; 2],
@@ -65,7 +42,7 @@ impl GameState {
     }
 }
 
-fn play_deterministic_game(p1_position: u32, p2_position: u32) -> u32 {
+pub fn play_deterministic_game(p1_position: u32, p2_position: u32) -> u32 {
     let mut game_state = GameState::new(p1_position, p2_position);
     let mut die = 0;
 
@@ -83,7 +60,7 @@ fn play_deterministic_game(p1_position: u32, p2_position: u32) -> u32 {
     game_state.min_score() * die
 }
 
-fn play_quantum_game(p1_position: u32, p2_position: u32) -> u64 {
+pub fn play_quantum_game(p1_position: u32, p2_position: u32) -> u64 {
     const ROLLS_AND_FREQUENCIES: [(u32, u64); 7] =
         [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
 
@@ -107,6 +84,28 @@ fn play_quantum_game(p1_position: u32, p2_position: u32) -> u64 {
     max(wins[0], wins[1])
 }
 
+pub struct Day21 {
+    p1_position: u32,
+    p2_position: u32,
+}
+
+impl Solution for Day21 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day21 {
+            p1_position: input.value(0)?.trim().parse()?,
+            p2_position: input.value(1)?.trim().parse()?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(play_deterministic_game(self.p1_position, self.p2_position).to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(play_quantum_game(self.p1_position, self.p2_position).to_string())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
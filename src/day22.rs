@@ -1,52 +1,10 @@
+use crate::solution::{Input, Solution};
 use self::Instruction::*;
 use std::cmp::{max, min};
-use std::fs::File;
-use std::io::BufRead;
 use std::str::FromStr;
-use std::{env, error, io};
+use std::error;
 
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let instructions: Vec<Instruction> = io::BufReader::new(File::open(path)?)
-            .lines()
-            .map(|line| Instruction::from_str(line.unwrap().as_str()).unwrap())
-            .collect();
-
-        {
-            let mut small_reactor = Reactor::new(Cuboid {
-                x: CoordinateRange { start: -50, end: 50 },
-                y: CoordinateRange { start: -50, end: 50 },
-                z: CoordinateRange { start: -50, end: 50 },
-            });
-
-            instructions.iter()
-                .for_each(|instruction| small_reactor.apply_instruction(instruction));
-
-            println!("Active cubes in small reactor: {}", small_reactor.active_cubes());
-        }
-
-        {
-            let mut large_reactor = Reactor::new(Cuboid {
-                x: CoordinateRange { start: i32::MIN, end: i32::MAX },
-                y: CoordinateRange { start: i32::MIN, end: i32::MAX },
-                z: CoordinateRange { start: i32::MIN, end: i32::MAX },
-            });
-
-            instructions.iter()
-                .for_each(|instruction| large_reactor.apply_instruction(instruction));
-
-            println!("Active cubes in large reactor: {}", large_reactor.active_cubes());
-        }
-
-        Ok(())
-    } else {
-        Err("Usage: day22 INPUT_FILE_PATH".into())
-    }
-}
-
-struct Reactor {
+pub struct Reactor {
     bounds: Cuboid,
     active_cuboids: Vec<Cuboid>,
 }
@@ -106,10 +64,10 @@ impl Reactor {
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-struct Cuboid {
-    x: CoordinateRange,
-    y: CoordinateRange,
-    z: CoordinateRange,
+pub struct Cuboid {
+    pub x: CoordinateRange,
+    pub y: CoordinateRange,
+    pub z: CoordinateRange,
 }
 
 impl Cuboid {
@@ -298,9 +256,9 @@ impl FromStr for Cuboid {
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-struct CoordinateRange {
-    start: i32,
-    end: i32,
+pub struct CoordinateRange {
+    pub start: i32,
+    pub end: i32,
 }
 
 impl CoordinateRange {
@@ -323,7 +281,7 @@ impl FromStr for CoordinateRange {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-enum Instruction {
+pub enum Instruction {
     On(Cuboid),
     Off(Cuboid),
 }
@@ -351,6 +309,53 @@ impl FromStr for Instruction {
     }
 }
 
+pub struct Day22 {
+    instructions: Vec<Instruction>,
+}
+
+impl Day22 {
+    fn active_cubes(&self, bounds: CoordinateRange) -> u64 {
+        let mut reactor = Reactor::new(Cuboid {
+            x: bounds,
+            y: bounds,
+            z: bounds,
+        });
+
+        self.instructions
+            .iter()
+            .for_each(|instruction| reactor.apply_instruction(instruction));
+
+        reactor.active_cubes()
+    }
+}
+
+impl Solution for Day22 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day22 {
+            instructions: input
+                .text()?
+                .lines()
+                .map(Instruction::from_str)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self
+            .active_cubes(CoordinateRange { start: -50, end: 50 })
+            .to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self
+            .active_cubes(CoordinateRange {
+                start: i32::MIN,
+                end: i32::MAX,
+            })
+            .to_string())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use indoc::indoc;
@@ -0,0 +1,1082 @@
+use crate::search::{self, SearchResult, State};
+use crate::solution::{Input, Solution};
+use self::Position::*;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+use std::str::FromStr;
+use std::error;
+
+// Amphipods are identified by species; species 0 is labeled 'A', species 1 is 'B', and so on, and
+// each species lives in the room with the same index (counting from the left).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Amphipod(u8);
+
+pub const A: Amphipod = Amphipod(0);
+pub const B: Amphipod = Amphipod(1);
+pub const C: Amphipod = Amphipod(2);
+pub const D: Amphipod = Amphipod(3);
+
+impl Amphipod {
+    fn from_char(c: char) -> Option<Self> {
+        if c.is_ascii_uppercase() {
+            Some(Amphipod(c as u8 - b'A'))
+        } else {
+            None
+        }
+    }
+
+    fn species(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl FromStr for Amphipod {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut chars = string.chars();
+
+        match (chars.next().and_then(Amphipod::from_char), chars.next()) {
+            (Some(amphipod), None) => Ok(amphipod),
+            _ => Err(format!("Bad amphipod identifier: {}", string).into()),
+        }
+    }
+}
+
+impl Display for Amphipod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", (b'A' + self.0) as char)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Position {
+    // Hallway spaces are numbered left to right starting from 0
+    Hallway(u32),
+
+    // Rooms are labeled by the kind of amphipod that wants to "live" there; within a room, spaces
+    // are numbered from the top down (0 is adjacent to the hallway).
+    Room(Amphipod, u32),
+}
+
+impl Position {
+    fn abs_diff(a: u32, b: u32) -> u32 {
+        a.abs_diff(b)
+    }
+}
+
+// The shape of a burrow: how long its hallway is, where its rooms are and how deep they go, and how
+// much energy each species spends per step
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BurrowGeometry {
+    hallway_length: u32,
+
+    // The hallway space just outside each room
+    room_entrances: Vec<u32>,
+    room_depth: u32,
+
+    step_costs: Vec<u32>,
+
+    // Amphipods may stop anywhere in the hallway except right outside a room
+    hallway_stops: Vec<u32>,
+}
+
+impl BurrowGeometry {
+    // Builds the geometry of a burrow with the given room entrances. Each species spends ten times
+    // as much energy per step as the one before it, starting at 1 for 'A'. That gets expensive
+    // quickly (the standard layout with two-space rooms runs out of room after eight species), so
+    // burrows with lots of species need cheaper steps from `with_custom_step_costs` instead.
+    pub fn new(
+        hallway_length: u32,
+        room_entrances: Vec<u32>,
+        room_depth: u32,
+    ) -> Result<Self, Box<dyn error::Error>> {
+        let step_costs = (0..room_entrances.len() as u32)
+            .map(|i| 10u32.checked_pow(i))
+            .collect::<Option<Vec<u32>>>()
+            .ok_or_else(|| {
+                format!("{} species is too many for the default step costs", room_entrances.len())
+            })?;
+
+        BurrowGeometry::with_custom_step_costs(
+            hallway_length,
+            room_entrances,
+            room_depth,
+            step_costs,
+        )
+    }
+
+    pub fn with_custom_step_costs(
+        hallway_length: u32,
+        room_entrances: Vec<u32>,
+        room_depth: u32,
+        step_costs: Vec<u32>,
+    ) -> Result<Self, Box<dyn error::Error>> {
+        assert!(
+            room_entrances.iter().all(|&entrance| entrance < hallway_length),
+            "Rooms must open onto the hallway"
+        );
+
+        assert_eq!(room_entrances.len(), step_costs.len(), "Every species needs a step cost");
+
+        let hallway_stops = (0..hallway_length)
+            .filter(|space| !room_entrances.contains(space))
+            .collect();
+
+        let geometry = BurrowGeometry {
+            hallway_length,
+            room_entrances,
+            room_depth,
+            step_costs,
+            hallway_stops,
+        };
+
+        geometry.check_step_costs()?;
+
+        Ok(geometry)
+    }
+
+    // The layout from the puzzle: rooms two spaces apart with a two-space alcove at each end of the
+    // hallway
+    pub fn standard(rooms: usize, room_depth: u32) -> Result<Self, Box<dyn error::Error>> {
+        let room_entrances = (0..rooms as u32).map(|room| (room + 1) * 2).collect();
+
+        BurrowGeometry::new((rooms as u32 * 2) + 3, room_entrances, room_depth)
+    }
+
+    pub fn with_step_costs(self, step_costs: Vec<u32>) -> Self {
+        assert_eq!(self.species(), step_costs.len(), "Every species needs a step cost");
+
+        let geometry = BurrowGeometry { step_costs, ..self };
+
+        if let Err(error) = geometry.check_step_costs() {
+            panic!("{}", error);
+        }
+
+        geometry
+    }
+
+    // Makes sure that no cost the search might add up can overflow. Every amphipod moves at most
+    // twice (out of a room, then into its own), and no move is longer than the hallway plus two
+    // rooms. A* also adds an estimate of the remaining cost, which is never more than that, to the
+    // cost so far.
+    fn check_step_costs(&self) -> Result<(), String> {
+        let longest_move = self.hallway_length as u128 + 2 * self.room_depth as u128;
+        let total_step_costs: u128 = self.step_costs.iter().map(|&cost| cost as u128).sum();
+        let most_expensive_solution = self.room_depth as u128 * 2 * longest_move * total_step_costs;
+
+        if 2 * most_expensive_solution > u32::MAX as u128 {
+            return Err(format!(
+                "Step costs {:?} could add up to more than {} energy",
+                self.step_costs,
+                u32::MAX
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn species(&self) -> usize {
+        self.room_entrances.len()
+    }
+
+    pub fn hallway_stops(&self) -> &[u32] {
+        &self.hallway_stops
+    }
+
+    pub fn distance(&self, start: Position, destination: Position) -> u32 {
+        match (start, destination) {
+            (Hallway(start), Hallway(dest)) => Position::abs_diff(start, dest),
+            (Hallway(start), Room(dest_amphipod, dest_position)) => {
+                let within_hallway = Position::abs_diff(start, self.room_entrance(dest_amphipod));
+                let from_hallway = dest_position + 1;
+
+                within_hallway + from_hallway
+            }
+            (Room(start_amphipod, start_position), Room(dest_amphipod, dest_position)) => {
+                if start_amphipod == dest_amphipod {
+                    Position::abs_diff(start_position, dest_position)
+                } else {
+                    let to_hallway = start_position + 1;
+                    let within_hallway = Position::abs_diff(
+                        self.room_entrance(start_amphipod),
+                        self.room_entrance(dest_amphipod),
+                    );
+                    let from_hallway = dest_position + 1;
+
+                    to_hallway + within_hallway + from_hallway
+                }
+            }
+            (Room(start_amphipod, start_position), Hallway(dest)) => {
+                let to_hallway = start_position + 1;
+                let within_hallway = Position::abs_diff(self.room_entrance(start_amphipod), dest);
+
+                to_hallway + within_hallway
+            }
+        }
+    }
+
+    pub fn room_entrance(&self, room: Amphipod) -> u32 {
+        self.room_entrances[room.species()]
+    }
+
+    fn cost_to_move(&self, amphipod: Amphipod, distance: u32) -> u32 {
+        distance * self.step_costs[amphipod.species()]
+    }
+
+    fn cell_count(&self) -> usize {
+        (self.hallway_length + (self.species() as u32 * self.room_depth)) as usize
+    }
+
+    // Each burrow keeps its occupants in a single list of cells: first the hallway from left to
+    // right, then each room from top to bottom
+    fn cell_index(&self, position: Position) -> usize {
+        match position {
+            Hallway(h) => h as usize,
+            Room(room, space) => {
+                (self.hallway_length + (room.0 as u32 * self.room_depth) + space) as usize
+            }
+        }
+    }
+
+    fn position(&self, cell_index: usize) -> Position {
+        let cell_index = cell_index as u32;
+
+        if cell_index < self.hallway_length {
+            Hallway(cell_index)
+        } else {
+            let room_cell = cell_index - self.hallway_length;
+            Room(
+                Amphipod((room_cell / self.room_depth) as u8),
+                room_cell % self.room_depth,
+            )
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Burrow {
+    geometry: Rc<BurrowGeometry>,
+    cells: Vec<Option<Amphipod>>,
+}
+
+impl Burrow {
+    // Builds a burrow with the standard layout. Amphipods are given in order of their starting
+    // positions from left to right and top to bottom. Example:
+    //
+    // #############
+    // #...........#
+    // ###B#C#B#D###  -> [B, A], [C, D], [B, C], [D, A]
+    //   #A#D#C#A#
+    //   #########
+    pub fn new<const N: usize, const R: usize>(initial_positions: [[Amphipod; N]; R]) -> Self {
+        let geometry = BurrowGeometry::standard(R, N as u32)
+            .unwrap_or_else(|error| panic!("{}", error));
+        let mut cells = vec![None; geometry.cell_count()];
+
+        for (room, occupants) in initial_positions.iter().enumerate() {
+            for (space, &amphipod) in occupants.iter().enumerate() {
+                cells[geometry.cell_index(Room(Amphipod(room as u8), space as u32))] =
+                    Some(amphipod);
+            }
+        }
+
+        let burrow = Burrow {
+            geometry: Rc::new(geometry),
+            cells,
+        };
+
+        assert!(burrow.has_full_species(), "Each species must fill exactly one room");
+
+        burrow
+    }
+
+    pub fn geometry(&self) -> &BurrowGeometry {
+        &self.geometry
+    }
+
+    // Returns this burrow with different per-step energy costs for each species
+    pub fn with_step_costs(self, step_costs: Vec<u32>) -> Self {
+        Burrow {
+            geometry: Rc::new(self.geometry.as_ref().clone().with_step_costs(step_costs)),
+            ..self
+        }
+    }
+
+    pub fn min_cost_to_resolve(&self) -> Option<u32> {
+        self.resolve().path.map(|path| path.cost)
+    }
+
+    // Searches for the cheapest way to settle this burrow with A*; the result also says how many
+    // states the search had to expand along the way
+    pub fn resolve(&self) -> SearchResult<Self> {
+        search::a_star(self.clone())
+    }
+
+    // Returns the moves that settle this burrow at the lowest possible cost, each paired with the
+    // state of the burrow just after that move
+    pub fn min_cost_moves(&self) -> Option<Vec<(Move, Self)>> {
+        self.resolve().path.map(|path| {
+            path.states
+                .windows(2)
+                .map(|states| (states[0].move_to(&states[1]), states[1].clone()))
+                .collect()
+        })
+    }
+
+    // Identifies the single move that turns this burrow into the given one
+    fn move_to(&self, next: &Self) -> Move {
+        let changed = |was_occupied: bool| {
+            (0..self.cells.len())
+                .find(|&i| {
+                    self.cells[i] != next.cells[i] && self.cells[i].is_some() == was_occupied
+                })
+                .unwrap()
+        };
+
+        let start = self.geometry.position(changed(true));
+        let destination = self.geometry.position(changed(false));
+        let amphipod = self.occupant(start).unwrap();
+
+        let cost = self
+            .geometry
+            .cost_to_move(amphipod, self.geometry.distance(start, destination));
+
+        (amphipod, start, destination, cost)
+    }
+
+    fn next_possible_states(&self) -> Vec<(Self, u32)> {
+        let mut next_possible_states = Vec::new();
+
+        for (amphipod, position) in self.positions() {
+            match position {
+                Hallway(h) => {
+                    // If we're in the hallway, the only legal move is into our target room if
+                    // it's empty or if it has one occupant of the correct type.
+                    let destination_room = self.geometry.room_entrance(amphipod);
+
+                    if self.hallway_path_clear(h, destination_room) {
+                        if let Some(space) = self.destination_space_within_room(amphipod) {
+                            next_possible_states
+                                .push(self.with_move(position, Room(amphipod, space)));
+                        }
+                    }
+                }
+                Room(room, space) => {
+                    // If we're in a room, there are a few possibilities:
+                    //
+                    // 1. We're in the right room, but are blocking somebody who needs to get
+                    // out, and we should move
+                    // 2. We're in the right room and are either in the back of the room or at
+                    // the front of the room with a roommate of the correct type, and we
+                    // shouldn't move
+                    // 3. We're in the wrong room and should move
+                    let should_move = if amphipod == room {
+                        // We're in the right room, but are we blocking somebody who wants to
+                        // get out?
+                        let occupants = self.room_occupants(room);
+
+                        occupants[space as usize..].iter().any(|&maybe_occupant| {
+                            if let Some(occupant) = maybe_occupant {
+                                occupant != room
+                            } else {
+                                false
+                            }
+                        })
+                    } else {
+                        // We're in the wrong room and should definitely move
+                        true
+                    };
+
+                    if should_move {
+                        // We know we SHOULD move, but can we?
+                        if self.path_to_hallway_clear(room, space) {
+                            // If we can make it into our target room, do it and consider no
+                            // other possible moves
+                            let start_room_position = self.geometry.room_entrance(room);
+                            let dest_room_position = self.geometry.room_entrance(amphipod);
+
+                            // The destination room may be ready for us, but if somebody's standing
+                            // in the hallway between here and there, we'll still need to step
+                            // aside into the hallway first
+                            let direct_move = self
+                                .destination_space_within_room(amphipod)
+                                .filter(|_| {
+                                    self.hallway_path_clear(start_room_position, dest_room_position)
+                                });
+
+                            if let Some(s) = direct_move {
+                                next_possible_states
+                                    .push(self.with_move(position, Room(amphipod, s)));
+                            } else {
+                                // Looks like we're moving to the hallway instead
+                                for &dest_hallway_position in self.geometry.hallway_stops() {
+                                    if self.hallway_path_clear(
+                                        start_room_position,
+                                        dest_hallway_position,
+                                    ) {
+                                        next_possible_states.push(
+                                            self.with_move(
+                                                position,
+                                                Hallway(dest_hallway_position),
+                                            ),
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        next_possible_states
+    }
+
+    fn hallway_path_clear(&self, start: u32, destination: u32) -> bool {
+        let hallway = &self.cells[..self.geometry.hallway_length as usize];
+
+        let hallway_slice = if start < destination {
+            &hallway[(start + 1) as usize..=destination as usize]
+        } else {
+            &hallway[destination as usize..start as usize]
+        };
+
+        hallway_slice.iter().all(Option::is_none)
+    }
+
+    fn path_to_hallway_clear(&self, room: Amphipod, space: u32) -> bool {
+        self.room_occupants(room)[0..space as usize]
+            .iter()
+            .all(Option::is_none)
+    }
+
+    fn room_occupants(&self, room: Amphipod) -> &[Option<Amphipod>] {
+        let top = self.geometry.cell_index(Room(room, 0));
+        &self.cells[top..top + self.geometry.room_depth as usize]
+    }
+
+    fn destination_space_within_room(&self, amphipod: Amphipod) -> Option<u32> {
+        let occupants = self.room_occupants(amphipod);
+
+        for space in (0..occupants.len()).rev() {
+            match occupants[space] {
+                None => return Some(space as u32),
+                Some(occupant) => {
+                    if occupant != amphipod {
+                        return None;
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn occupant(&self, position: Position) -> Option<Amphipod> {
+        self.cells[self.geometry.cell_index(position)]
+    }
+
+    fn with_move(&self, start: Position, destination: Position) -> (Self, u32) {
+        let amphipod = self.occupant(start).unwrap();
+
+        let mut with_move = self.clone();
+        with_move.cells[self.geometry.cell_index(start)] = None;
+        with_move.cells[self.geometry.cell_index(destination)] = Some(amphipod);
+
+        let cost = self
+            .geometry
+            .cost_to_move(amphipod, self.geometry.distance(start, destination));
+
+        (with_move, cost)
+    }
+
+    fn positions(&self) -> Vec<(Amphipod, Position)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(i, cell)| cell.map(|amphipod| (amphipod, self.geometry.position(i))))
+            .collect()
+    }
+
+    fn is_settled(&self) -> bool {
+        self.positions().iter().all(|(amphipod, position)| {
+            if let Room(room, _) = position {
+                amphipod == room
+            } else {
+                false
+            }
+        })
+    }
+
+    // A burrow can only ever settle if there are exactly enough amphipods of each species to fill
+    // its room
+    fn has_full_species(&self) -> bool {
+        let mut counts = vec![0; self.geometry.species()];
+
+        for (amphipod, _) in self.positions() {
+            match counts.get_mut(amphipod.species()) {
+                Some(count) => *count += 1,
+                None => return false,
+            }
+        }
+
+        counts
+            .iter()
+            .all(|&count| count == self.geometry.room_depth)
+    }
+}
+
+// Burrows are only ever compared within a single search of a single geometry, so there's no need
+// to compare (or hash) the geometry itself
+impl PartialEq for Burrow {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl Eq for Burrow {}
+
+impl Hash for Burrow {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cells.hash(state);
+    }
+}
+
+// An amphipod, where it started, where it ended up and how much energy it took to get there
+pub type Move = (Amphipod, Position, Position, u32);
+
+// Reads a burrow diagram like the one in the puzzle. The hallway is the run of open spaces on the
+// second line, rooms are the open spaces on the lines below it, and the room entrances (and
+// therefore the legal places to stop in the hallway) follow from where the rooms line up with the
+// hallway.
+impl FromStr for Burrow {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let is_open = |c: char| c == '.' || c.is_ascii_uppercase();
+        let open_columns = |line: &str| -> Vec<usize> {
+            line.char_indices()
+                .filter(|&(_, c)| is_open(c))
+                .map(|(col, _)| col)
+                .collect()
+        };
+
+        let lines: Vec<&str> = string.lines().collect();
+        let hallway_columns = open_columns(lines.get(1).ok_or("Missing hallway")?);
+
+        let hallway_start = *hallway_columns.first().ok_or("Empty hallway")?;
+        let hallway_length = hallway_columns.len() as u32;
+
+        if hallway_columns.last() != Some(&(hallway_start + hallway_columns.len() - 1)) {
+            return Err("Hallway must be a single unbroken row".into());
+        }
+
+        let room_rows: Vec<&str> = lines[2..]
+            .iter()
+            .copied()
+            .take_while(|line| line.chars().any(is_open))
+            .collect();
+
+        let room_columns = open_columns(room_rows.first().ok_or("Missing rooms")?);
+
+        if room_rows.iter().any(|row| open_columns(row) != room_columns) {
+            return Err("Rooms must all be the same depth".into());
+        }
+
+        let room_entrances = room_columns
+            .iter()
+            .map(|&col| {
+                col.checked_sub(hallway_start)
+                    .map(|entrance| entrance as u32)
+                    .filter(|&entrance| entrance < hallway_length)
+                    .ok_or("Rooms must open onto the hallway")
+            })
+            .collect::<Result<Vec<u32>, _>>()?;
+
+        let geometry = BurrowGeometry::new(hallway_length, room_entrances, room_rows.len() as u32)?;
+        let mut cells = vec![None; geometry.cell_count()];
+
+        let hallway = lines[1].chars().skip(hallway_start).take(hallway_length as usize);
+
+        for (h, c) in hallway.enumerate() {
+            cells[geometry.cell_index(Hallway(h as u32))] = Amphipod::from_char(c);
+        }
+
+        for (space, row) in room_rows.iter().enumerate() {
+            for (room, &col) in room_columns.iter().enumerate() {
+                let c = row.chars().nth(col).unwrap();
+                cells[geometry.cell_index(Room(Amphipod(room as u8), space as u32))] =
+                    Amphipod::from_char(c);
+            }
+        }
+
+        let burrow = Burrow {
+            geometry: Rc::new(geometry),
+            cells,
+        };
+
+        if burrow.has_full_species() {
+            Ok(burrow)
+        } else {
+            Err("Each species must have exactly enough amphipods to fill one room".into())
+        }
+    }
+}
+
+// Draws the burrow in the same layout that `Burrow::from_str` reads
+impl Display for Burrow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let geometry = &self.geometry;
+        let width = geometry.hallway_length as usize + 2;
+
+        let cell_char = |position| match self.occupant(position) {
+            Some(amphipod) => (b'A' + amphipod.0) as char,
+            None => '.',
+        };
+
+        let hallway: String = (0..geometry.hallway_length)
+            .map(|h| cell_char(Hallway(h)))
+            .collect();
+
+        writeln!(f, "{}", "#".repeat(width))?;
+        writeln!(f, "#{}#", hallway)?;
+
+        // Below the top row of rooms, the walls only extend as far as the outermost rooms
+        let first_wall = *geometry.room_entrances.iter().min().unwrap() as usize;
+        let last_wall = *geometry.room_entrances.iter().max().unwrap() as usize + 2;
+
+        for space in 0..=geometry.room_depth {
+            let mut row: Vec<char> = if space == 0 {
+                vec!['#'; width]
+            } else {
+                (0..=last_wall)
+                    .map(|col| if col < first_wall { ' ' } else { '#' })
+                    .collect()
+            };
+
+            if space < geometry.room_depth {
+                for room in 0..geometry.species() {
+                    let amphipod = Amphipod(room as u8);
+                    row[geometry.room_entrance(amphipod) as usize + 1] =
+                        cell_char(Room(amphipod, space));
+                }
+            }
+
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl State for Burrow {
+    fn successors(&self) -> Vec<(Self, u32)> {
+        self.next_possible_states()
+    }
+
+    fn is_goal(&self) -> bool {
+        self.is_settled()
+    }
+
+    // Every amphipod that isn't in its own room will need to walk at least as far as the top space
+    // of its room. Amphipods already home (even if they'll need to step aside for somebody) add
+    // nothing, so this never overestimates.
+    fn heuristic(&self) -> u32 {
+        self.positions()
+            .iter()
+            .map(|&(amphipod, position)| {
+                let distance = match position {
+                    Room(room, _) if room == amphipod => 0,
+                    _ => self.geometry.distance(position, Room(amphipod, 0)),
+                };
+
+                self.geometry.cost_to_move(amphipod, distance)
+            })
+            .sum()
+    }
+}
+
+pub struct Day23 {
+    folded: Burrow,
+    unfolded: Burrow,
+}
+
+impl Solution for Day23 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day23 {
+            folded: Burrow::from_str(input.value(0)?)?,
+            unfolded: Burrow::from_str(input.value(1)?)?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        let cost = self.folded.min_cost_to_resolve().ok_or("No solution")?;
+        Ok(cost.to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        let cost = self.unfolded.min_cost_to_resolve().ok_or("No solution")?;
+        Ok(cost.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_abs_diff() {
+        assert_eq!(2, Position::abs_diff(5, 3));
+        assert_eq!(2, Position::abs_diff(3, 5));
+    }
+
+    #[test]
+    fn test_distance() {
+        let geometry = BurrowGeometry::standard(4, 2).unwrap();
+
+        assert_eq!(4, geometry.distance(Hallway(1), Hallway(5)));
+        assert_eq!(4, geometry.distance(Hallway(5), Hallway(1)));
+        assert_eq!(5, geometry.distance(Hallway(1), Room(B, 1)));
+        assert_eq!(5, geometry.distance(Room(B, 1), Hallway(1)));
+        assert_eq!(1, geometry.distance(Room(A, 0), Room(A, 1)));
+        assert_eq!(5, geometry.distance(Room(A, 0), Room(B, 1)));
+
+        assert_eq!(0, geometry.distance(Hallway(1), Hallway(1)));
+        assert_eq!(0, geometry.distance(Room(A, 0), Room(A, 0)));
+    }
+
+    #[test]
+    fn test_room_occupants() {
+        let mut burrow = Burrow::new([[D, A], [B, B], [C, C], [A, D]]);
+
+        assert_eq!([Some(D), Some(A)], burrow.room_occupants(A));
+
+        // Move D into the hallway
+        burrow = burrow.with_move(Room(A, 0), Hallway(0)).0;
+        assert_eq!([None, Some(A)], burrow.room_occupants(A));
+
+        // Move A into the hallway
+        burrow = burrow.with_move(Room(A, 1), Hallway(1)).0;
+        assert_eq!([None, None], burrow.room_occupants(A));
+    }
+
+    #[test]
+    fn test_hallway_path_clear() {
+        let mut burrow = Burrow::new([[D, A], [B, B], [C, C], [A, D]]);
+
+        assert!(burrow.hallway_path_clear(0, 10));
+
+        burrow = burrow.with_move(Room(A, 0), Hallway(5)).0;
+
+        assert!(!burrow.hallway_path_clear(0, 10));
+        assert!(!burrow.hallway_path_clear(10, 0));
+        assert!(burrow.hallway_path_clear(0, 4));
+        assert!(!burrow.hallway_path_clear(0, 5));
+        assert!(burrow.hallway_path_clear(5, 0));
+        assert!(burrow.hallway_path_clear(5, 10));
+        assert!(!burrow.hallway_path_clear(10, 5));
+    }
+
+    #[test]
+    fn test_destination_space_within_room() {
+        let mut burrow = Burrow::new([[D, A], [B, C], [C, B], [A, D]]);
+
+        assert!(burrow.destination_space_within_room(A).is_none());
+
+        // Move D from the A room into the hallway
+        burrow = burrow.with_move(Room(A, 0), Hallway(0)).0;
+
+        assert_eq!(0, burrow.destination_space_within_room(A).unwrap());
+
+        // Move A from the A room into the hallway
+        burrow = burrow.with_move(Room(A, 1), Hallway(1)).0;
+
+        assert_eq!(1, burrow.destination_space_within_room(A).unwrap());
+
+        // Move B from the B room into the hallway, leaving C in place
+        burrow = burrow.with_move(Room(B, 0), Hallway(10)).0;
+
+        assert!(burrow.destination_space_within_room(B).is_none());
+    }
+
+    #[test]
+    fn test_path_to_hallway_clear() {
+        let mut burrow = Burrow::new([[D, A], [B, C], [C, B], [A, D]]);
+
+        assert!(burrow.path_to_hallway_clear(A, 0));
+        assert!(!burrow.path_to_hallway_clear(A, 1));
+
+        // Move D from the A room into the hallway
+        burrow = burrow.with_move(Room(A, 0), Hallway(0)).0;
+
+        assert!(burrow.path_to_hallway_clear(A, 1));
+    }
+
+    #[test]
+    fn test_with_move() {
+        let burrow = Burrow::new([[D, A], [B, C], [C, B], [A, D]]);
+
+        let expected_burrow = Burrow::from_str(indoc! {"
+            #############
+            #D..........#
+            ###.#B#C#A###
+              #A#C#B#D#
+              #########
+        "})
+        .unwrap();
+
+        let expected_cost = 3000;
+
+        assert_eq!(
+            (expected_burrow, expected_cost),
+            burrow.with_move(Room(A, 0), Hallway(0))
+        );
+    }
+
+    #[test]
+    fn test_next_possible_states() {
+        assert!(Burrow::new([[A, A], [B, B], [C, C], [D, D]])
+            .next_possible_states()
+            .is_empty());
+
+        assert!(!Burrow::new([[D, A], [B, C], [C, B], [A, D]])
+            .next_possible_states()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_is_settled() {
+        assert!(Burrow::new([[A, A], [B, B], [C, C], [D, D]]).is_settled());
+        assert!(!Burrow::new([[D, A], [B, C], [C, B], [A, D]]).is_settled());
+    }
+
+    #[test]
+    fn test_min_cost_to_resolve() {
+        assert_eq!(
+            Some(12521),
+            Burrow::new([[B, A], [C, D], [B, C], [D, A]]).min_cost_to_resolve()
+        );
+
+        assert_eq!(
+            Some(44169),
+            Burrow::new([[B, D, D, A], [C, C, B, D], [B, B, A, C], [D, A, C, A]])
+                .min_cost_to_resolve()
+        );
+    }
+
+    #[test]
+    fn test_heuristic() {
+        assert_eq!(0, Burrow::new([[A, A], [B, B], [C, C], [D, D]]).heuristic());
+
+        // D and A have swapped rooms, and each needs to travel at least 8 spaces to get home
+        assert_eq!(8008, Burrow::new([[D, A], [B, B], [C, C], [A, D]]).heuristic());
+    }
+
+    #[test]
+    fn test_resolve() {
+        for burrow in [
+            Burrow::new([[B, A], [C, D], [B, C], [D, A]]),
+            Burrow::new([[D, A], [C, B], [B, C], [A, D]]),
+        ] {
+            let a_star = burrow.resolve();
+            let dijkstra = search::dijkstra(burrow);
+
+            assert_eq!(
+                dijkstra.path.map(|path| path.cost),
+                a_star.path.map(|path| path.cost)
+            );
+
+            assert!(a_star.expanded < dijkstra.expanded);
+        }
+    }
+
+    #[test]
+    fn test_min_cost_moves() {
+        let burrow = Burrow::new([[B, A], [C, D], [B, C], [D, A]]);
+        let moves = burrow.min_cost_moves().unwrap();
+
+        assert_eq!(
+            12521,
+            moves.iter().map(|&((_, _, _, cost), _)| cost).sum::<u32>()
+        );
+
+        assert!(moves.last().unwrap().1.is_settled());
+
+        // Each move should pick up where the last one left off
+        let mut previous = burrow;
+
+        for ((amphipod, start, destination, cost), next) in moves {
+            assert_eq!((next.clone(), cost), previous.with_move(start, destination));
+            assert_eq!(amphipod, previous.occupant(start).unwrap());
+
+            previous = next;
+        }
+
+        assert_eq!(Some(Vec::new()), previous.min_cost_moves());
+    }
+
+    #[test]
+    fn test_burrow_display() {
+        let burrow_string = indoc! {"
+            #############
+            #...........#
+            ###B#C#B#D###
+              #A#D#C#A#
+              #########
+        "};
+
+        assert_eq!(burrow_string, Burrow::from_str(burrow_string).unwrap().to_string());
+
+        let (burrow, _) =
+            Burrow::new([[D, A], [B, C], [C, B], [A, D]]).with_move(Room(A, 0), Hallway(0));
+
+        assert_eq!(
+            indoc! {"
+                #############
+                #D..........#
+                ###.#B#C#A###
+                  #A#C#B#D#
+                  #########
+            "},
+            burrow.to_string()
+        );
+    }
+
+    #[test]
+    fn test_hallway_stops() {
+        let geometry = BurrowGeometry::standard(4, 2).unwrap();
+        assert_eq!(&[0, 1, 3, 5, 7, 9, 10], geometry.hallway_stops());
+
+        let burrow = Burrow::from_str(indoc! {"
+            ##########
+            #........#
+            ###A#B#C##
+              #A#B#C#
+              #######
+        "})
+        .unwrap();
+
+        assert_eq!(3, burrow.geometry().species());
+        assert_eq!(&[0, 1, 3, 5, 7], burrow.geometry().hallway_stops());
+    }
+
+    #[test]
+    fn test_general_geometry() {
+        // Two species, one-space rooms and a seven-space hallway
+        let burrow = Burrow::new([[B], [A]]);
+
+        assert_eq!(
+            indoc! {"
+                #########
+                #.......#
+                ###B#A###
+                  #####
+            "},
+            burrow.to_string()
+        );
+
+        // The only space between the rooms would block the swap, so A steps out to the right (2),
+        // B goes straight home (40) and A walks back around (4)
+        assert_eq!(Some(46), burrow.min_cost_to_resolve());
+        assert_eq!(Some(10), burrow.with_step_costs(vec![1, 1]).min_cost_to_resolve());
+
+        // Three species with three-space rooms, with an extra space at the right end of the hallway
+        let burrow = Burrow::from_str(indoc! {"
+            ##########
+            #........#
+            ###B#C#A##
+              #A#B#C#
+              #C#A#B#
+              #######
+        "})
+        .unwrap();
+
+        assert_eq!(Some(burrow.clone()), burrow.to_string().parse().ok());
+        assert!(burrow.min_cost_to_resolve().is_some());
+
+        // Amphipods already in the hallway are part of the diagram, too
+        let burrow = Burrow::from_str(indoc! {"
+            #########
+            #B.....A#
+            ###.#.###
+              #A#B#
+              #####
+        "})
+        .unwrap();
+
+        assert_eq!(Some(5 + 50), burrow.min_cost_to_resolve());
+
+        assert!(Burrow::from_str(indoc! {"
+            #########
+            #.......#
+            ###B#A###
+              #A#A#
+              #####
+        "})
+        .is_err());
+    }
+
+    #[test]
+    fn test_many_species() {
+        assert!(BurrowGeometry::standard(8, 2).is_ok());
+        assert!(BurrowGeometry::standard(9, 2).is_err());
+        assert!(BurrowGeometry::standard(11, 2).is_err());
+
+        // Eleven species only get step costs when they're spelled out, and then only if they're
+        // cheap enough
+        let entrances: Vec<u32> = (1..=11).map(|room| room * 2).collect();
+        let geometry =
+            BurrowGeometry::with_custom_step_costs(25, entrances.clone(), 2, (1..=11).collect())
+                .unwrap();
+
+        assert_eq!(11, geometry.species());
+        assert!(
+            BurrowGeometry::with_custom_step_costs(25, entrances, 2, vec![1 << 24; 11]).is_err()
+        );
+
+        let rooms = ('A'..='K').map(String::from).collect::<Vec<_>>().join("#");
+        let diagram = format!(
+            "{}\n#{}#\n###{}###\n  #{}#\n  {}\n",
+            "#".repeat(27),
+            ".".repeat(25),
+            rooms,
+            rooms,
+            "#".repeat(23)
+        );
+
+        let error = Burrow::from_str(&diagram).unwrap_err();
+        assert!(error.to_string().contains("too many"), "{}", error);
+    }
+
+    #[test]
+    fn test_burrow_from_string() {
+        assert_eq!(
+            Burrow::new([[B, A], [C, D], [B, C], [D, A]]),
+            Burrow::from_str(indoc! {"
+                #############
+                #...........#
+                ###B#C#B#D###
+                  #A#D#C#A#
+                  #########
+            "})
+            .unwrap()
+        );
+
+        assert_eq!(
+            Burrow::new([[B, D, D, A], [C, C, B, D], [B, B, A, C], [D, A, C, A]]),
+            Burrow::from_str(indoc! {"
+                #############
+                #...........#
+                ###B#C#B#D###
+                  #D#C#B#A#
+                  #D#B#A#C#
+                  #A#D#C#A#
+                  #########
+            "})
+            .unwrap()
+        );
+    }
+}
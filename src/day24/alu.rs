@@ -0,0 +1,629 @@
+use self::Instruction::*;
+use self::Register::*;
+use self::Value::*;
+use std::collections::VecDeque;
+use std::error;
+use std::fmt::{Display, Formatter};
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+impl Register {
+    pub const ALL: [Register; 4] = [W, X, Y, Z];
+
+    #[inline]
+    pub fn index(&self) -> usize {
+        match self {
+            W => 0,
+            X => 1,
+            Y => 2,
+            Z => 3,
+        }
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            W => write!(f, "w"),
+            X => write!(f, "x"),
+            Y => write!(f, "y"),
+            Z => write!(f, "z"),
+        }
+    }
+}
+
+impl FromStr for Register {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "w" => Ok(Register::W),
+            "x" => Ok(Register::X),
+            "y" => Ok(Register::Y),
+            "z" => Ok(Register::Z),
+            _ => Err(format!("Not a valid register: {}", s).into()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Value {
+    Literal(i64),
+    Register(Register),
+}
+
+impl Value {
+    #[inline]
+    pub fn eval(&self, registers: &[i64; 4]) -> i64 {
+        match self {
+            Literal(literal) => *literal,
+            Register(register) => registers[register.index()],
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Literal(literal) => write!(f, "{}", literal),
+            Register(register) => write!(f, "{}", register),
+        }
+    }
+}
+
+impl FromStr for Value {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(register) = Register::from_str(s) {
+            Ok(Register(register))
+        } else if let Ok(literal) = i64::from_str(s) {
+            Ok(Literal(literal))
+        } else {
+            Err(format!("Not a valid value: {}", s).into())
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Instruction {
+    Input(Register),
+    Add(Register, Value),
+    Multiply(Register, Value),
+    Divide(Register, Value),
+    Modulo(Register, Value),
+    Compare(Register, Value),
+}
+
+impl FromStr for Instruction {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut components = s.split_whitespace();
+
+        let instruction: Result<&str, &str> = components.next().ok_or("No instruction");
+
+        let register: Result<Result<Register, Box<dyn std::error::Error>>, &str> = components
+            .next()
+            .ok_or("No register")
+            .map(Register::from_str);
+
+        let value: Result<Result<Value, Box<dyn std::error::Error>>, &str> = components
+            .next()
+            .ok_or("No value")
+            .map(Value::from_str);
+
+        match instruction? {
+            "inp" => Ok(Input(register??)),
+            "add" => Ok(Add(register??, value??)),
+            "mul" => Ok(Multiply(register??, value??)),
+            "div" => Ok(Divide(register??, value??)),
+            "mod" => Ok(Modulo(register??, value??)),
+            "eql" => Ok(Compare(register??, value??)),
+            _ => Err(format!("Unrecognized instruction: {}", instruction?).into()),
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Input(register) => write!(f, "inp {}", register),
+            Add(register, value) => write!(f, "add {} {}", register, value),
+            Multiply(register, value) => write!(f, "mul {} {}", register, value),
+            Divide(register, value) => write!(f, "div {} {}", register, value),
+            Modulo(register, value) => write!(f, "mod {} {}", register, value),
+            Compare(register, value) => write!(f, "eql {} {}", register, value),
+        }
+    }
+}
+
+// Things that stop a program partway through. Each carries the index of the offending instruction.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AluError {
+    MissingInput(usize),
+    DivisionByZero(usize),
+    // `mod a b` is only defined for a >= 0 and b > 0
+    InvalidModulo(usize),
+    // The result doesn't fit in a 64-bit register
+    Overflow(usize),
+}
+
+impl Display for AluError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AluError::MissingInput(index) => write!(f, "Instruction {}: no input to read", index),
+            AluError::DivisionByZero(index) => write!(f, "Instruction {}: division by zero", index),
+            AluError::InvalidModulo(index) => {
+                write!(f, "Instruction {}: modulo with a negative or zero operand", index)
+            }
+            AluError::Overflow(index) => write!(f, "Instruction {}: arithmetic overflow", index),
+        }
+    }
+}
+
+impl error::Error for AluError {}
+
+pub struct ArithmeticLogicUnit {
+    instructions: Vec<Instruction>,
+}
+
+impl ArithmeticLogicUnit {
+    pub fn new(instructions: Vec<Instruction>) -> Self {
+        ArithmeticLogicUnit { instructions }
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    pub fn execute(&self, inputs: &[i64]) -> Result<[i64; 4], AluError> {
+        let mut machine = Machine::new(self);
+        inputs.iter().for_each(|&input| machine.provide_input(input));
+
+        while machine.step()?.is_some() {}
+
+        Ok(machine.registers())
+    }
+
+    // Runs the program to completion, returning the state of the registers after each instruction
+    pub fn trace(&self, inputs: &[i64]) -> Result<Vec<TraceEntry>, AluError> {
+        let mut machine = Machine::new(self);
+        inputs.iter().for_each(|&input| machine.provide_input(input));
+
+        let mut trace = Vec::new();
+
+        while let Some(entry) = machine.step()? {
+            trace.push(entry);
+        }
+
+        Ok(trace)
+    }
+}
+
+// A single executed instruction and the registers just after it
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TraceEntry {
+    pub index: usize,
+    pub instruction: Instruction,
+    pub registers: [i64; 4],
+}
+
+impl Display for TraceEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>4}  {:<12}  {}",
+            self.index,
+            self.instruction.to_string(),
+            format_registers(&self.registers)
+        )
+    }
+}
+
+// Why a run stopped: either the next instruction reads input, or there are no more instructions
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Stop {
+    Input(usize),
+    Halted,
+}
+
+// A program in the middle of running, which can be advanced one instruction at a time or run until
+// it's about to read its next input
+pub struct Machine<'a> {
+    instructions: &'a [Instruction],
+    registers: [i64; 4],
+    next_index: usize,
+    inputs: VecDeque<i64>,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(alu: &'a ArithmeticLogicUnit) -> Self {
+        Machine {
+            instructions: &alu.instructions,
+            registers: [0; 4],
+            next_index: 0,
+            inputs: VecDeque::new(),
+        }
+    }
+
+    // Queues a value for a future `inp` instruction to read
+    pub fn provide_input(&mut self, input: i64) {
+        self.inputs.push_back(input);
+    }
+
+    pub fn registers(&self) -> [i64; 4] {
+        self.registers
+    }
+
+    pub fn register(&self, register: Register) -> i64 {
+        self.registers[register.index()]
+    }
+
+    pub fn next_index(&self) -> usize {
+        self.next_index
+    }
+
+    pub fn next_instruction(&self) -> Option<Instruction> {
+        self.instructions.get(self.next_index).copied()
+    }
+
+    // Executes the next instruction, returning `None` if the program has already finished. A
+    // failed instruction leaves the registers untouched, so it can be retried (after providing
+    // input, for example).
+    pub fn step(&mut self) -> Result<Option<TraceEntry>, AluError> {
+        let index = self.next_index;
+
+        let instruction = match self.next_instruction() {
+            Some(instruction) => instruction,
+            None => return Ok(None),
+        };
+
+        let registers = &mut self.registers;
+
+        match instruction {
+            Input(register) => {
+                registers[register.index()] =
+                    self.inputs.pop_front().ok_or(AluError::MissingInput(index))?;
+            }
+            Add(register, value) => {
+                registers[register.index()] = registers[register.index()]
+                    .checked_add(value.eval(registers))
+                    .ok_or(AluError::Overflow(index))?;
+            }
+            Multiply(register, value) => {
+                registers[register.index()] = registers[register.index()]
+                    .checked_mul(value.eval(registers))
+                    .ok_or(AluError::Overflow(index))?;
+            }
+            Divide(register, value) => {
+                let divisor = value.eval(registers);
+
+                if divisor == 0 {
+                    return Err(AluError::DivisionByZero(index));
+                }
+
+                registers[register.index()] = registers[register.index()]
+                    .checked_div(divisor)
+                    .ok_or(AluError::Overflow(index))?;
+            }
+            Modulo(register, value) => {
+                let divisor = value.eval(registers);
+
+                if registers[register.index()] < 0 || divisor <= 0 {
+                    return Err(AluError::InvalidModulo(index));
+                }
+
+                registers[register.index()] %= divisor;
+            }
+            Compare(register, value) => {
+                registers[register.index()] =
+                    if registers[register.index()] == value.eval(registers) {
+                        1
+                    } else {
+                        0
+                    }
+            }
+        }
+
+        self.next_index += 1;
+
+        Ok(Some(TraceEntry {
+            index,
+            instruction,
+            registers: self.registers,
+        }))
+    }
+
+    // Runs at least one instruction, then keeps going until the program is about to execute an
+    // `inp` instruction (a breakpoint) or finishes
+    pub fn run_to_breakpoint(&mut self) -> Result<Stop, AluError> {
+        self.step()?;
+
+        loop {
+            match self.next_instruction() {
+                None => return Ok(Stop::Halted),
+                Some(Input(_)) => return Ok(Stop::Input(self.next_index)),
+                Some(_) => {
+                    self.step()?;
+                }
+            }
+        }
+    }
+}
+
+fn format_registers(registers: &[i64; 4]) -> String {
+    Register::ALL
+        .iter()
+        .map(|register| format!("{}={}", register, registers[register.index()]))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+const REPL_HELP: &str = "Commands:
+    <number>    provide the next input and run to the next inp instruction
+    step [N]    execute the next N instructions (default 1), showing registers after each
+    regs        show the registers
+    reset       start over from the first instruction
+    help        show this message
+    quit        exit";
+
+// Runs an interactive session that feeds input values to the program one at a time, stopping
+// before each `inp` instruction so the registers can be inspected
+pub fn repl(
+    alu: &ArithmeticLogicUnit,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    let mut machine = Machine::new(alu);
+
+    writeln!(output, "{}", REPL_HELP)?;
+    report_position(&machine, &mut output)?;
+
+    for line in input.lines() {
+        let line = line?;
+        let mut words = line.split_whitespace();
+
+        match (words.next(), words.next()) {
+            (None, _) => continue,
+            (Some("quit" | "q"), _) => break,
+            (Some("help" | "h"), _) => writeln!(output, "{}", REPL_HELP)?,
+            (Some("regs" | "r"), _) => {
+                writeln!(output, "{}", format_registers(&machine.registers()))?
+            }
+            (Some("reset"), _) => {
+                machine = Machine::new(alu);
+                report_position(&machine, &mut output)?;
+            }
+            (Some("step" | "s"), count) => match count.unwrap_or("1").parse::<usize>() {
+                Ok(count) => {
+                    for _ in 0..count {
+                        match machine.step() {
+                            Ok(Some(entry)) => writeln!(output, "{}", entry)?,
+                            Ok(None) => break,
+                            Err(error) => {
+                                writeln!(output, "{}", error)?;
+                                break;
+                            }
+                        }
+                    }
+
+                    report_position(&machine, &mut output)?;
+                }
+                Err(_) => writeln!(output, "Not a valid step count: {}", count.unwrap())?,
+            },
+            (Some(value), None) if value.parse::<i64>().is_ok() => {
+                machine.provide_input(value.parse().unwrap());
+
+                if let Err(error) = machine.run_to_breakpoint() {
+                    writeln!(output, "{}", error)?;
+                }
+
+                writeln!(output, "{}", format_registers(&machine.registers()))?;
+                report_position(&machine, &mut output)?;
+            }
+            _ => writeln!(output, "Unrecognized command: {}", line.trim())?,
+        }
+    }
+
+    Ok(())
+}
+
+fn report_position(machine: &Machine, output: &mut impl Write) -> io::Result<()> {
+    match machine.next_instruction() {
+        Some(instruction) => writeln!(
+            output,
+            "Paused before instruction {}: {}",
+            machine.next_index(),
+            instruction
+        ),
+        None => writeln!(output, "Halted"),
+    }
+}
+
+impl FromStr for ArithmeticLogicUnit {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let instructions = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(Instruction::from_str)
+            .collect::<Result<Vec<Instruction>, Box<dyn error::Error>>>()?;
+
+        Ok(ArithmeticLogicUnit { instructions })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_instruction_from_string() {
+        assert_eq!(Input(W), Instruction::from_str("inp w").unwrap());
+        assert_eq!(Add(X, Literal(4)), Instruction::from_str("add x 4").unwrap());
+        assert_eq!(Multiply(Y, Register(Z)), Instruction::from_str("mul y z").unwrap());
+        assert_eq!(Divide(Z, Literal(-7)), Instruction::from_str("div z -7").unwrap());
+        assert_eq!(Modulo(W, Register(X)), Instruction::from_str("mod w x").unwrap());
+        assert_eq!(Compare(X, Register(Y)), Instruction::from_str("eql x y").unwrap());
+    }
+
+    #[test]
+    fn test_execute() {
+        {
+            let alu = ArithmeticLogicUnit {
+                instructions: vec![Input(X), Multiply(X, Literal(-1))],
+            };
+
+            assert_eq!(Ok([0, -4, 0, 0]), alu.execute(&[4]));
+        }
+
+        {
+            let alu = ArithmeticLogicUnit {
+                instructions: vec![
+                    Input(Z),
+                    Input(X),
+                    Multiply(Z, Literal(3)),
+                    Compare(Z, Register(X)),
+                ],
+            };
+
+            assert_eq!(Ok([0, 12, 0, 1]), alu.execute(&[4, 12]));
+            assert_eq!(Ok([0, 13, 0, 0]), alu.execute(&[4, 13]));
+        }
+
+        {
+            let alu = ArithmeticLogicUnit {
+                instructions: vec![
+                    Input(W),
+                    Add(Z, Register(W)),
+                    Modulo(Z, Literal(2)),
+                    Divide(W, Literal(2)),
+                    Add(Y, Register(W)),
+                    Modulo(Y, Literal(2)),
+                    Divide(W, Literal(2)),
+                    Add(X, Register(W)),
+                    Modulo(X, Literal(2)),
+                    Divide(W, Literal(2)),
+                    Modulo(W, Literal(2)),
+                ],
+            };
+
+            assert_eq!(Ok([1, 0, 1, 0]), alu.execute(&[0b1010]));
+            assert_eq!(Ok([0, 1, 0, 1]), alu.execute(&[0b0101]));
+        }
+    }
+
+    #[test]
+    fn test_alu_from_string() {
+        let alu = ArithmeticLogicUnit::from_str("inp x\nmul x -1\n").unwrap();
+
+        assert_eq!(&[Input(X), Multiply(X, Literal(-1))], alu.instructions());
+        assert!(ArithmeticLogicUnit::from_str("inp x\nsub x 1\n").is_err());
+    }
+
+    #[test]
+    fn test_execute_errors() {
+        let alu = ArithmeticLogicUnit::from_str("inp x\ninp y\ndiv x y\nmod y x\n").unwrap();
+
+        assert_eq!(Err(AluError::MissingInput(1)), alu.execute(&[4]));
+        assert_eq!(Err(AluError::DivisionByZero(2)), alu.execute(&[4, 0]));
+        assert_eq!(Err(AluError::InvalidModulo(3)), alu.execute(&[4, -3]));
+        assert_eq!(Err(AluError::InvalidModulo(3)), alu.execute(&[-8, 1]));
+        assert_eq!(Ok([0, 1, 0, 0]), alu.execute(&[5, 3]));
+
+        let alu = ArithmeticLogicUnit::from_str("inp z\nmul z z\nadd z z\n").unwrap();
+
+        assert_eq!(Err(AluError::Overflow(1)), alu.execute(&[i64::MAX]));
+        assert_eq!(Err(AluError::Overflow(2)), alu.execute(&[3_000_000_000]));
+        assert_eq!(Ok([0, 0, 0, 18]), alu.execute(&[3]));
+
+        let alu = ArithmeticLogicUnit::from_str("inp x\ninp y\ndiv x y\n").unwrap();
+        assert_eq!(Err(AluError::Overflow(2)), alu.execute(&[i64::MIN, -1]));
+    }
+
+    #[test]
+    fn test_instruction_display() {
+        for instruction in ["inp w", "add x -4", "mul y z", "div z 26", "mod w 2", "eql x w"] {
+            assert_eq!(instruction, Instruction::from_str(instruction).unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn test_trace() {
+        let alu = ArithmeticLogicUnit::from_str("inp x\nmul x -1\n").unwrap();
+        let trace = alu.trace(&[4]).unwrap();
+
+        assert_eq!(
+            vec![
+                TraceEntry {
+                    index: 0,
+                    instruction: Input(X),
+                    registers: [0, 4, 0, 0],
+                },
+                TraceEntry {
+                    index: 1,
+                    instruction: Multiply(X, Literal(-1)),
+                    registers: [0, -4, 0, 0],
+                },
+            ],
+            trace
+        );
+
+        assert_eq!("   1  mul x -1      w=0 x=-4 y=0 z=0", trace[1].to_string());
+    }
+
+    #[test]
+    fn test_run_to_breakpoint() {
+        let alu = ArithmeticLogicUnit::from_str("inp x\nadd z x\ninp y\nmul z y\n").unwrap();
+        let mut machine = Machine::new(&alu);
+
+        assert_eq!(Some(Input(X)), machine.next_instruction());
+        assert_eq!(Err(AluError::MissingInput(0)), machine.run_to_breakpoint());
+
+        machine.provide_input(3);
+        assert_eq!(Ok(Stop::Input(2)), machine.run_to_breakpoint());
+        assert_eq!(3, machine.register(Z));
+
+        machine.provide_input(5);
+        assert_eq!(Ok(Stop::Halted), machine.run_to_breakpoint());
+        assert_eq!([0, 3, 5, 15], machine.registers());
+
+        assert_eq!(Ok(None), machine.step());
+    }
+
+    #[test]
+    fn test_repl() {
+        let alu = ArithmeticLogicUnit::from_str("inp x\nadd z x\ninp y\nmul z y\n").unwrap();
+        let mut output = Vec::new();
+
+        repl(&alu, "3\nregs\nstep\n5\nbogus\nquit\n".as_bytes(), &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let transcript: Vec<&str> = output.lines().skip(REPL_HELP.lines().count()).collect();
+
+        assert_eq!(
+            vec![
+                "Paused before instruction 0: inp x",
+                "w=0 x=3 y=0 z=3",
+                "Paused before instruction 2: inp y",
+                "w=0 x=3 y=0 z=3",
+                "Instruction 2: no input to read",
+                "Paused before instruction 2: inp y",
+                "w=0 x=3 y=5 z=15",
+                "Halted",
+                "Unrecognized command: bogus",
+            ],
+            transcript
+        );
+    }
+}
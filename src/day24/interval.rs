@@ -0,0 +1,339 @@
+use crate::day24::alu::Instruction::*;
+use crate::day24::alu::{AluError, ArithmeticLogicUnit, Register, Value};
+use std::fmt::{Display, Formatter};
+
+// An inclusive range of values a register might hold. The arithmetic here follows the ALU's rules
+// (division truncates toward zero, and `mod` is only defined for non-negative dividends and
+// positive divisors), and saturates rather than overflowing. That's only an approximation: the
+// ALU stops with `AluError::Overflow` instead, so a result that has saturated covers values no
+// run could actually reach, and can make a run that would overflow look like it finishes.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Interval {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl Interval {
+    pub const DIGIT: Interval = Interval { min: 1, max: 9 };
+
+    pub fn new(min: i64, max: i64) -> Self {
+        assert!(min <= max, "Interval must not be empty");
+        Interval { min, max }
+    }
+
+    pub fn constant(value: i64) -> Self {
+        Interval::new(value, value)
+    }
+
+    pub fn as_constant(&self) -> Option<i64> {
+        if self.min == self.max {
+            Some(self.min)
+        } else {
+            None
+        }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.min <= value && value <= self.max
+    }
+
+    pub fn is_within(&self, other: &Interval) -> bool {
+        other.min <= self.min && self.max <= other.max
+    }
+
+    pub fn intersects(&self, other: &Interval) -> bool {
+        self.min <= other.max && other.min <= self.max
+    }
+
+    pub fn union(&self, other: &Interval) -> Self {
+        Interval::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    pub fn add(&self, other: &Interval) -> Self {
+        Interval::new(
+            self.min.saturating_add(other.min),
+            self.max.saturating_add(other.max),
+        )
+    }
+
+    pub fn multiply(&self, other: &Interval) -> Self {
+        Interval::spanning(&[
+            self.min.saturating_mul(other.min),
+            self.min.saturating_mul(other.max),
+            self.max.saturating_mul(other.min),
+            self.max.saturating_mul(other.max),
+        ])
+    }
+
+    // Returns `None` if the divisor can only be zero. A divisor that merely might be zero is
+    // treated as if it were any of its other values.
+    pub fn divide(&self, divisor: &Interval) -> Option<Self> {
+        let quotients = |divisor: Interval| {
+            Interval::spanning(&[
+                self.min.saturating_div(divisor.min),
+                self.min.saturating_div(divisor.max),
+                self.max.saturating_div(divisor.min),
+                self.max.saturating_div(divisor.max),
+            ])
+        };
+
+        let negative = Some(divisor.min)
+            .filter(|&min| min < 0)
+            .map(|min| quotients(Interval::new(min, divisor.max.min(-1))));
+
+        let positive = Some(divisor.max)
+            .filter(|&max| max > 0)
+            .map(|max| quotients(Interval::new(divisor.min.max(1), max)));
+
+        match (negative, positive) {
+            (Some(negative), Some(positive)) => Some(negative.union(&positive)),
+            (negative, positive) => negative.or(positive),
+        }
+    }
+
+    // Returns `None` if there are no values for which `mod` is defined
+    pub fn modulo(&self, divisor: &Interval) -> Option<Self> {
+        if self.max < 0 || divisor.max <= 0 {
+            return None;
+        }
+
+        let dividend = Interval::new(self.min.max(0), self.max);
+        let divisor = Interval::new(divisor.min.max(1), divisor.max);
+
+        if dividend.max < divisor.min {
+            // The dividend is always smaller than the divisor, so it passes through unchanged
+            Some(dividend)
+        } else if divisor.as_constant().is_some()
+            && dividend.min / divisor.min == dividend.max / divisor.min
+        {
+            // The dividend doesn't wrap around, so the remainders keep their order
+            Some(Interval::new(dividend.min % divisor.min, dividend.max % divisor.min))
+        } else {
+            Some(Interval::new(0, dividend.max.min(divisor.max - 1)))
+        }
+    }
+
+    pub fn equal(&self, other: &Interval) -> Self {
+        match (self.as_constant(), other.as_constant()) {
+            (Some(a), Some(b)) if a == b => Interval::constant(1),
+            _ if !self.intersects(other) => Interval::constant(0),
+            _ => Interval::new(0, 1),
+        }
+    }
+
+    fn spanning(values: &[i64]) -> Self {
+        Interval::new(
+            *values.iter().min().unwrap(),
+            *values.iter().max().unwrap(),
+        )
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}]", self.min, self.max)
+    }
+}
+
+// Runs an ALU program over intervals instead of values, so that a single pass covers every input
+// the program might read. Each input is a digit from 1 to 9 unless it's been provided.
+#[derive(Clone)]
+pub struct IntervalMachine<'a> {
+    alu: &'a ArithmeticLogicUnit,
+    registers: [Interval; 4],
+    next_index: usize,
+}
+
+impl<'a> IntervalMachine<'a> {
+    pub fn new(alu: &'a ArithmeticLogicUnit) -> Self {
+        IntervalMachine {
+            alu,
+            registers: [Interval::constant(0); 4],
+            next_index: 0,
+        }
+    }
+
+    pub fn registers(&self) -> [Interval; 4] {
+        self.registers
+    }
+
+    pub fn register(&self, register: Register) -> Interval {
+        self.registers[register.index()]
+    }
+
+    pub fn next_index(&self) -> usize {
+        self.next_index
+    }
+
+    // Reads the given digit at the program's next `inp` instruction, then runs until it's about to
+    // read another one
+    pub fn provide_digit(&mut self, digit: i64) -> Result<(), AluError> {
+        self.run_to_input()?;
+
+        if self.next_index < self.alu.instructions().len() {
+            self.step(Interval::constant(digit))?;
+        }
+
+        self.run_to_input()
+    }
+
+    // Runs the rest of the program, returning the range of values each register might end up with
+    pub fn final_registers(&self) -> Result<[Interval; 4], AluError> {
+        let mut machine = self.clone();
+
+        while machine.next_index < machine.alu.instructions().len() {
+            machine.step(Interval::DIGIT)?;
+        }
+
+        Ok(machine.registers)
+    }
+
+    fn run_to_input(&mut self) -> Result<(), AluError> {
+        while let Some(instruction) = self.alu.instructions().get(self.next_index) {
+            if matches!(instruction, Input(_)) {
+                break;
+            }
+
+            self.step(Interval::DIGIT)?;
+        }
+
+        Ok(())
+    }
+
+    // Executes the next instruction, with `input` as the range of values an `inp` might read. Only
+    // operations that fail for every value in their operands' ranges are errors.
+    fn step(&mut self, input: Interval) -> Result<(), AluError> {
+        let index = self.next_index;
+        let registers = &mut self.registers;
+
+        let value = |value: &Value, registers: &[Interval; 4]| match value {
+            Value::Literal(literal) => Interval::constant(*literal),
+            Value::Register(register) => registers[register.index()],
+        };
+
+        match &self.alu.instructions()[index] {
+            Input(register) => registers[register.index()] = input,
+            Add(register, b) => {
+                registers[register.index()] = registers[register.index()].add(&value(b, registers));
+            }
+            Multiply(register, b) => {
+                registers[register.index()] =
+                    registers[register.index()].multiply(&value(b, registers));
+            }
+            Divide(register, b) => {
+                registers[register.index()] = registers[register.index()]
+                    .divide(&value(b, registers))
+                    .ok_or(AluError::DivisionByZero(index))?;
+            }
+            Modulo(register, b) => {
+                registers[register.index()] = registers[register.index()]
+                    .modulo(&value(b, registers))
+                    .ok_or(AluError::InvalidModulo(index))?;
+            }
+            Compare(register, b) => {
+                registers[register.index()] =
+                    registers[register.index()].equal(&value(b, registers));
+            }
+        }
+
+        self.next_index += 1;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Interval::new(-3, 5);
+        let b = Interval::new(2, 4);
+
+        assert_eq!(Interval::new(-1, 9), a.add(&b));
+        assert_eq!(Interval::new(-12, 20), a.multiply(&b));
+        assert_eq!(Interval::new(-1, 2), a.divide(&b).unwrap());
+        assert_eq!(
+            Interval::new(-5, 5),
+            a.divide(&Interval::new(-1, 1)).unwrap()
+        );
+        assert_eq!(None, a.divide(&Interval::constant(0)));
+
+        assert_eq!(Interval::new(0, 3), a.modulo(&b).unwrap());
+        assert_eq!(
+            Interval::new(1, 9),
+            Interval::DIGIT.modulo(&Interval::constant(26)).unwrap()
+        );
+        assert_eq!(None, Interval::new(-3, -1).modulo(&b));
+        assert_eq!(
+            Interval::new(1, 3),
+            Interval::new(27, 29).modulo(&Interval::constant(26)).unwrap()
+        );
+
+        let large = Interval::new(i64::MAX - 1, i64::MAX);
+        assert_eq!(large, large.add(&Interval::new(0, 1)));
+        assert_eq!(
+            Interval::new(0, i64::MAX),
+            Interval::new(i64::MIN, 0).divide(&Interval::constant(-1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_equal() {
+        assert_eq!(
+            Interval::constant(1),
+            Interval::constant(3).equal(&Interval::constant(3))
+        );
+        assert_eq!(Interval::constant(0), Interval::new(10, 35).equal(&Interval::DIGIT));
+        assert_eq!(Interval::new(0, 1), Interval::new(7, 15).equal(&Interval::DIGIT));
+    }
+
+    #[test]
+    fn test_interval_machine() {
+        let alu = ArithmeticLogicUnit::from_str(indoc! {"
+            inp w
+            add z w
+            mul z 26
+            inp x
+            add x 10
+            eql x w
+            add z x
+            inp y
+            mod y 5
+            div z y
+        "})
+        .unwrap();
+
+        let mut machine = IntervalMachine::new(&alu);
+
+        assert_eq!(
+            [
+                Interval::DIGIT,
+                Interval::constant(0),
+                Interval::new(0, 4),
+                Interval::new(6, 234),
+            ],
+            machine.final_registers().unwrap()
+        );
+
+        machine.provide_digit(3).unwrap();
+        assert_eq!(Interval::constant(78), machine.register(Register::Z));
+
+        machine.provide_digit(7).unwrap();
+        assert_eq!(Interval::constant(0), machine.register(Register::X));
+        assert_eq!(
+            Interval::new(19, 78),
+            machine.final_registers().unwrap()[Register::Z.index()]
+        );
+
+        let alu = ArithmeticLogicUnit::from_str("inp w\nmod w 5\ndiv z w").unwrap();
+        // The divisor might be zero here, but only fails for certain once we know the digit
+        assert!(IntervalMachine::new(&alu).final_registers().is_ok());
+
+        let mut machine = IntervalMachine::new(&alu);
+        assert_eq!(Err(AluError::DivisionByZero(2)), machine.provide_digit(5));
+    }
+}
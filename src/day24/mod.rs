@@ -0,0 +1,377 @@
+use crate::day24::alu::Instruction::*;
+use crate::day24::alu::Register::*;
+use crate::day24::alu::Value::*;
+use crate::day24::alu::{ArithmeticLogicUnit, Instruction};
+use crate::day24::interval::{Interval, IntervalMachine};
+use crate::solution::{Input, Solution};
+use std::collections::HashSet;
+use std::error;
+use std::str::FromStr;
+pub mod alu;
+pub mod interval;
+pub mod symbolic;
+
+// The MONAD program repeats the same block of instructions for each digit of the model number;
+// only the literals in `div z`, `add x` and `add y` change from one block to the next (they're
+// zero here).
+const MONAD_BLOCK: [Instruction; 18] = [
+    Input(W),
+    Multiply(X, Literal(0)),
+    Add(X, Register(Z)),
+    Modulo(X, Literal(26)),
+    Divide(Z, Literal(0)),
+    Add(X, Literal(0)),
+    Compare(X, Register(W)),
+    Compare(X, Literal(0)),
+    Multiply(Y, Literal(0)),
+    Add(Y, Literal(25)),
+    Multiply(Y, Register(X)),
+    Add(Y, Literal(1)),
+    Multiply(Z, Register(Y)),
+    Multiply(Y, Literal(0)),
+    Add(Y, Register(W)),
+    Add(Y, Literal(0)),
+    Multiply(Y, Register(X)),
+    Add(Z, Register(Y)),
+];
+
+// The parameters of a single digit's block of MONAD instructions. `shift_right` is true if the
+// block divides z by 26 (rather than by 1), `a` is added to x and `b` is added to y (see
+// `Monad::checksum`).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DigitParameters {
+    pub shift_right: bool,
+    pub a: i64,
+    pub b: i64,
+}
+
+impl DigitParameters {
+    fn from_block(block: &[Instruction]) -> Option<Self> {
+        if block.len() != MONAD_BLOCK.len() {
+            return None;
+        }
+
+        let mut shift_right = None;
+        let mut a = None;
+        let mut b = None;
+
+        for (i, (&instruction, &expected)) in block.iter().zip(MONAD_BLOCK.iter()).enumerate() {
+            match (i, instruction) {
+                (4, Divide(Z, Literal(1))) => shift_right = Some(false),
+                (4, Divide(Z, Literal(26))) => shift_right = Some(true),
+                (5, Add(X, Literal(literal))) => a = Some(literal),
+                (15, Add(Y, Literal(literal))) => b = Some(literal),
+                _ if instruction == expected => {}
+                _ => return None,
+            }
+        }
+
+        Some(DigitParameters {
+            shift_right: shift_right?,
+            a: a?,
+            b: b?,
+        })
+    }
+}
+
+// A model number validator, reduced to the parameters of each of its digits
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Monad {
+    parameters: Vec<DigitParameters>,
+}
+
+impl Monad {
+    pub fn from_program(alu: &ArithmeticLogicUnit) -> Result<Self, Box<dyn error::Error>> {
+        let instructions = alu.instructions();
+
+        if instructions.is_empty() || !instructions.len().is_multiple_of(MONAD_BLOCK.len()) {
+            return Err("Program isn't made of MONAD digit blocks".into());
+        }
+
+        let parameters = instructions
+            .chunks(MONAD_BLOCK.len())
+            .enumerate()
+            .map(|(digit, block)| {
+                DigitParameters::from_block(block).ok_or_else(|| {
+                    format!("Block for digit {} doesn't look like a MONAD block", digit)
+                })
+            })
+            .collect::<Result<Vec<DigitParameters>, String>>()?;
+
+        Ok(Monad { parameters })
+    }
+
+    pub fn parameters(&self) -> &[DigitParameters] {
+        &self.parameters
+    }
+
+    pub fn checksum(&self, digits: &[i64]) -> i64 {
+        // So where did all this come from? This is the result of "decompiling" the ALU
+        // instructions from the problem input. The main goal was just to produce something where
+        // the operation of the model number verification doodad was easier to read and
+        // understand, but this also has the nice side effect of making it much faster to run.
+        //
+        // It turns out that the same set of instructions get repeated for each digit, but with
+        // different parameters/coefficients in a few places (see `MONAD_BLOCK`). These
+        // coefficients are specific to each puzzle input, and we pull them out of the program
+        // when we parse it.
+        let mut checksum = 0;
+
+        for (parameters, &digit) in self.parameters.iter().zip(digits) {
+            // A few key observations here:
+            //
+            // 1. Unless the digit in a given slot matches a specific value (which may or may not be
+            // between 1 and 9), `shift_left` will be true and the checksum stored in register Z
+            // will increase.
+            // 2. There are a fixed number of cases where the checksum will get divided (shifted
+            // back to the right). This is the ONLY way we can move the checksum in Z back toward
+            // zero.
+            // 3. Conveniently enough, the blocks that shift right are also the ONLY opportunities
+            // we get to avoid the left shift mentioned in #1 above (the others add a big enough A
+            // that no digit can match).
+            // 4. In the puzzle inputs, half of the blocks shift right, which means there are as
+            // many opportunities to avoid expanding the checksum in Z as there are cases where we
+            // can't avoid expanding it, and in turn THAT means that we need to hit every
+            // not-expanding opportunity to have a chance at getting the checksum back down to
+            // zero.
+            //
+            // We don't have to lean on any of that to find model numbers, though; the interval
+            // analysis in `explore_model_number` works out when a prefix has missed its chance
+            // straight from the program.
+            let shift_left = (checksum % 26) + parameters.a != digit;
+
+            if parameters.shift_right {
+                checksum /= 26;
+            }
+
+            if shift_left {
+                checksum *= 26;
+                checksum += digit + parameters.b
+            }
+        }
+
+        checksum
+    }
+}
+
+impl FromStr for Monad {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Monad::from_program(&ArithmeticLogicUnit::from_str(s)?)
+    }
+}
+
+pub fn largest_valid_model_number(program: &ArithmeticLogicUnit) -> Option<u64> {
+    find_model_number(program, Direction::Descending)
+}
+
+pub fn smallest_valid_model_number(program: &ArithmeticLogicUnit) -> Option<u64> {
+    find_model_number(program, Direction::Ascending)
+}
+
+// A model number is valid if the program leaves 0 in z after reading each of its digits. This
+// works for any program that checks model numbers that way, not just ones built from
+// `MONAD_BLOCK`. A program that never reads a digit has no model numbers at all.
+fn find_model_number(program: &ArithmeticLogicUnit, direction: Direction) -> Option<u64> {
+    let len = program
+        .instructions()
+        .iter()
+        .filter(|instruction| matches!(instruction, Input(_)))
+        .count();
+
+    let machine = IntervalMachine::new(program);
+
+    explore_model_number(&machine, &[], len, direction, &mut HashSet::new())
+        .map(|digits| to_model_number(&digits))
+}
+
+// Tries each possible next digit in turn (in the given direction), abandoning any prefix for which
+// interval analysis shows that z can't end up at 0 no matter which digits follow. Different
+// prefixes often leave the machine in the same state, so we remember the states (the next
+// instruction and the registers) that we know lead nowhere.
+pub fn explore_model_number(
+    machine: &IntervalMachine,
+    preceding_digits: &[i64],
+    len: usize,
+    direction: Direction,
+    dead_ends: &mut HashSet<(usize, [Interval; 4])>,
+) -> Option<Vec<i64>> {
+    if preceding_digits.len() >= len {
+        return None;
+    }
+
+    let state = (machine.next_index(), machine.registers());
+
+    if dead_ends.contains(&state) {
+        return None;
+    }
+
+    for next_digit in direction.digits() {
+        let mut candidate_digits = Vec::from(preceding_digits);
+        candidate_digits.push(next_digit);
+
+        let mut candidate_machine = machine.clone();
+
+        let might_be_valid = candidate_machine.provide_digit(next_digit).is_ok()
+            && matches!(
+                candidate_machine.final_registers(),
+                Ok(registers) if registers[Z.index()].contains(0)
+            );
+
+        if !might_be_valid {
+            continue;
+        }
+
+        if candidate_digits.len() == len {
+            return Some(candidate_digits);
+        }
+
+        if let Some(valid_model_number) = explore_model_number(
+            &candidate_machine,
+            &candidate_digits,
+            len,
+            direction,
+            dead_ends,
+        ) {
+            return Some(valid_model_number);
+        }
+    }
+
+    dead_ends.insert(state);
+    None
+}
+
+fn to_model_number(digits: &[i64]) -> u64 {
+    digits
+        .iter()
+        .fold(0, |model_number, digit| (model_number * 10) + *digit as u64)
+}
+
+#[derive(Copy, Clone)]
+pub enum Direction {
+    Ascending,
+    Descending,
+}
+
+impl Direction {
+    // The digits a model number can contain, in the order we should try them
+    pub fn digits(self) -> [i64; 9] {
+        let mut digits = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+        if matches!(self, Direction::Descending) {
+            digits.reverse();
+        }
+
+        digits
+    }
+}
+
+pub struct Day24 {
+    program: ArithmeticLogicUnit,
+}
+
+impl Solution for Day24 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day24 {
+            program: ArithmeticLogicUnit::from_str(input.text()?)?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        let model_number =
+            largest_valid_model_number(&self.program).ok_or("No valid model number")?;
+
+        Ok(model_number.to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        let model_number =
+            smallest_valid_model_number(&self.program).ok_or("No valid model number")?;
+
+        Ok(model_number.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_INSTRUCTIONS: &str = include_str!("../../data/day24.txt");
+
+    #[test]
+    fn test_digits() {
+        assert_eq!([1, 2, 3, 4, 5, 6, 7, 8, 9], Direction::Ascending.digits());
+        assert_eq!([9, 8, 7, 6, 5, 4, 3, 2, 1], Direction::Descending.digits());
+    }
+
+    #[test]
+    fn test_monad_from_program() {
+        // These were originally decompiled from this input by hand
+        const A: [i64; 14] = [11, 13, 15, -8, 13, 15, -11, -4, -15, 14, 14, -1, -8, -14];
+        const B: [i64; 14] = [6, 14, 14, 10, 9, 12, 8, 13, 12, 6, 9, 15, 4, 10];
+
+        let monad = Monad::from_str(TEST_INSTRUCTIONS).unwrap();
+
+        assert_eq!(14, monad.parameters().len());
+
+        for (i, parameters) in monad.parameters().iter().enumerate() {
+            assert_eq!(A[i], parameters.a);
+            assert_eq!(B[i], parameters.b);
+            assert_eq!(A[i] < 0, parameters.shift_right);
+        }
+
+        assert!(Monad::from_str("inp w\nadd z w\n").is_err());
+        assert!(Monad::from_str(&TEST_INSTRUCTIONS.replacen("add y 25", "add y 24", 1)).is_err());
+    }
+
+    #[test]
+    fn test_checksum() {
+        let alu = ArithmeticLogicUnit::from_str(TEST_INSTRUCTIONS).unwrap();
+        let monad = Monad::from_program(&alu).unwrap();
+
+        for inputs in [
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 1, 2, 3, 4, 5],
+            [9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9],
+            [9, 8, 7, 6, 5, 4, 3, 2, 1, 9, 8, 7, 6, 5],
+        ] {
+            assert_eq!(alu.execute(&inputs).unwrap()[3], monad.checksum(&inputs));
+        }
+    }
+
+    #[test]
+    fn test_valid_model_numbers() {
+        let alu = ArithmeticLogicUnit::from_str(TEST_INSTRUCTIONS).unwrap();
+
+        assert_eq!(Some(99394899891971), largest_valid_model_number(&alu));
+        assert_eq!(Some(92171126131911), smallest_valid_model_number(&alu));
+
+        // Not a MONAD program, but it checks its digits the same way; z is 0 when d0 == d1 + 2 and
+        // d2 == 4
+        let alu = ArithmeticLogicUnit::from_str(indoc! {"
+            inp w
+            add z w
+            inp x
+            add x 2
+            mul x -1
+            add z x
+            inp y
+            add y -4
+            mul y 20
+            add z y
+        "})
+        .unwrap();
+
+        assert!(Monad::from_program(&alu).is_err());
+        assert_eq!(Some(974), largest_valid_model_number(&alu));
+        assert_eq!(Some(314), smallest_valid_model_number(&alu));
+
+        let alu = ArithmeticLogicUnit::from_str("inp w\nadd z w").unwrap();
+        assert_eq!(None, largest_valid_model_number(&alu));
+
+        let alu = ArithmeticLogicUnit::from_str("add z 0").unwrap();
+        assert_eq!(None, largest_valid_model_number(&alu));
+        assert_eq!(None, smallest_valid_model_number(&alu));
+    }
+}
@@ -0,0 +1,655 @@
+use crate::day24::alu::{AluError, ArithmeticLogicUnit, Instruction, Register, Value};
+use crate::day24::interval::Interval;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+// An operation over the input digits d0, d1, ...; operands are shared rather than copied because
+// each block of a MONAD-style program refers to the previous block's `z` several times
+#[derive(Debug)]
+pub enum Operation {
+    Constant(i64),
+    Digit(usize),
+    // A named intermediate result, like the value of `z` just before the program reads a digit
+    Variable(String, Rc<Expression>),
+    Add(Rc<Expression>, Rc<Expression>),
+    Multiply(Rc<Expression>, Rc<Expression>),
+    Divide(Rc<Expression>, Rc<Expression>),
+    Modulo(Rc<Expression>, Rc<Expression>),
+    Equal(Rc<Expression>, Rc<Expression>),
+    NotEqual(Rc<Expression>, Rc<Expression>),
+}
+
+#[derive(Debug)]
+pub struct Expression {
+    operation: Operation,
+    // Every value the expression can take if each digit is between 1 and 9
+    interval: Interval,
+}
+
+impl Expression {
+    pub fn operation(&self) -> &Operation {
+        &self.operation
+    }
+
+    pub fn interval(&self) -> Interval {
+        self.interval
+    }
+
+    pub fn as_constant(&self) -> Option<i64> {
+        match self.operation {
+            Operation::Constant(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn evaluate(&self, digits: &[i64]) -> i64 {
+        // Shared sub-expressions only need evaluating once
+        self.evaluate_cached(digits, &mut HashMap::new())
+    }
+
+    fn evaluate_cached(&self, digits: &[i64], cache: &mut HashMap<*const Expression, i64>) -> i64 {
+        if let Some(&value) = cache.get(&(self as *const Expression)) {
+            return value;
+        }
+
+        let mut evaluate = |expression: &Rc<Expression>| expression.evaluate_cached(digits, cache);
+
+        let value = match &self.operation {
+            Operation::Constant(value) => *value,
+            Operation::Digit(index) => digits[*index],
+            Operation::Variable(_, expression) => evaluate(expression),
+            Operation::Add(a, b) => evaluate(a) + evaluate(b),
+            Operation::Multiply(a, b) => evaluate(a) * evaluate(b),
+            Operation::Divide(a, b) => evaluate(a) / evaluate(b),
+            Operation::Modulo(a, b) => evaluate(a) % evaluate(b),
+            Operation::Equal(a, b) => (evaluate(a) == evaluate(b)) as i64,
+            Operation::NotEqual(a, b) => (evaluate(a) != evaluate(b)) as i64,
+        };
+
+        cache.insert(self as *const Expression, value);
+        value
+    }
+
+    fn is_trivial(&self) -> bool {
+        matches!(
+            self.operation,
+            Operation::Constant(_) | Operation::Digit(_) | Operation::Variable(_, _)
+        )
+    }
+
+    // Looks through any variables to the expression they name
+    fn resolve(self: &Rc<Self>) -> &Rc<Self> {
+        match &self.operation {
+            Operation::Variable(_, expression) => expression.resolve(),
+            _ => self,
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self.operation {
+            Operation::Equal(_, _) | Operation::NotEqual(_, _) => 1,
+            Operation::Add(_, _) => 2,
+            Operation::Multiply(_, _) | Operation::Divide(_, _) | Operation::Modulo(_, _) => 3,
+            _ => 4,
+        }
+    }
+
+    fn operands(&self) -> Option<(&Rc<Expression>, &Rc<Expression>)> {
+        match &self.operation {
+            Operation::Add(a, b)
+            | Operation::Multiply(a, b)
+            | Operation::Divide(a, b)
+            | Operation::Modulo(a, b)
+            | Operation::Equal(a, b)
+            | Operation::NotEqual(a, b) => Some((a, b)),
+            _ => None,
+        }
+    }
+}
+
+fn constant(value: i64) -> Rc<Expression> {
+    Rc::new(Expression {
+        operation: Operation::Constant(value),
+        interval: Interval::constant(value),
+    })
+}
+
+fn digit(index: usize) -> Rc<Expression> {
+    Rc::new(Expression {
+        operation: Operation::Digit(index),
+        interval: Interval::DIGIT,
+    })
+}
+
+fn variable(name: String, expression: Rc<Expression>) -> Rc<Expression> {
+    let interval = expression.interval;
+
+    Rc::new(Expression {
+        operation: Operation::Variable(name, expression),
+        interval,
+    })
+}
+
+// Anything whose interval has narrowed to a single value is just that value
+fn operation(operation: Operation, interval: Interval) -> Rc<Expression> {
+    match interval.as_constant() {
+        Some(value) => constant(value),
+        None => Rc::new(Expression {
+            operation,
+            interval,
+        }),
+    }
+}
+
+// Constant arithmetic that overflows fails the same way it would in the ALU
+fn add(a: Rc<Expression>, b: Rc<Expression>, index: usize) -> Result<Rc<Expression>, AluError> {
+    Ok(match (a.as_constant(), b.as_constant()) {
+        (Some(a), Some(b)) => constant(a.checked_add(b).ok_or(AluError::Overflow(index))?),
+        (Some(_), None) => add(b, a, index)?,
+        (_, Some(0)) => a,
+        (_, Some(b_value)) => match &a.operation {
+            // Gather constants up so that `d0 + 14 - 8` becomes `d0 + 6`. The ALU never adds the
+            // constants to each other, so if that would overflow, leave them be.
+            Operation::Add(inner, c)
+                if c.as_constant().and_then(|c| c.checked_add(b_value)).is_some() =>
+            {
+                let sum = c.as_constant().unwrap() + b_value;
+                add(inner.clone(), constant(sum), index)?
+            }
+            _ => operation(Operation::Add(a.clone(), b.clone()), a.interval.add(&b.interval)),
+        },
+        _ => {
+            let interval = a.interval.add(&b.interval);
+            operation(Operation::Add(a, b), interval)
+        }
+    })
+}
+
+fn multiply(
+    a: Rc<Expression>,
+    b: Rc<Expression>,
+    index: usize,
+) -> Result<Rc<Expression>, AluError> {
+    Ok(match (a.as_constant(), b.as_constant()) {
+        (Some(a), Some(b)) => constant(a.checked_mul(b).ok_or(AluError::Overflow(index))?),
+        (Some(_), None) => multiply(b, a, index)?,
+        (_, Some(0)) => constant(0),
+        (_, Some(1)) => a,
+        _ => {
+            let interval = a.interval.multiply(&b.interval);
+            operation(Operation::Multiply(a, b), interval)
+        }
+    })
+}
+
+// If `a` is a sum of `q * m` and other terms, with `q` non-negative and the other terms adding up
+// to between 0 and m - 1, returns `q` and the sum of the other terms; that's how a MONAD-style
+// program pushes a digit onto `z`
+fn split_multiple(
+    a: &Rc<Expression>,
+    m: i64,
+    index: usize,
+) -> Option<(Rc<Expression>, Rc<Expression>)> {
+    let mut terms = Vec::new();
+    collect_terms(a.resolve(), &mut terms);
+
+    let is_multiple = |term: &Rc<Expression>| match &term.resolve().operation {
+        Operation::Multiply(quotient, multiplier) => {
+            multiplier.as_constant() == Some(m) && quotient.interval.min >= 0
+        }
+        _ => false,
+    };
+
+    let position = terms.iter().position(is_multiple)?;
+    let quotient = match &terms.remove(position).resolve().operation {
+        Operation::Multiply(quotient, _) => quotient.clone(),
+        _ => unreachable!(),
+    };
+
+    let remainder = terms
+        .into_iter()
+        .try_fold(constant(0), |sum, term| add(sum, term, index))
+        .ok()?;
+
+    if remainder.interval.is_within(&Interval::new(0, m - 1)) {
+        Some((quotient, remainder))
+    } else {
+        None
+    }
+}
+
+fn collect_terms(expression: &Rc<Expression>, terms: &mut Vec<Rc<Expression>>) {
+    match &expression.operation {
+        Operation::Add(a, b) => {
+            collect_terms(a, terms);
+            collect_terms(b, terms);
+        }
+        _ => terms.push(expression.clone()),
+    }
+}
+
+fn divide(a: Rc<Expression>, b: Rc<Expression>, index: usize) -> Result<Rc<Expression>, AluError> {
+    let interval = a
+        .interval
+        .divide(&b.interval)
+        .ok_or(AluError::DivisionByZero(index))?;
+
+    Ok(match (a.as_constant(), b.as_constant()) {
+        (Some(a), Some(b)) => constant(a.checked_div(b).ok_or(AluError::Overflow(index))?),
+        (_, Some(1)) => a,
+        (_, Some(m)) if m > 1 => match split_multiple(&a, m, index) {
+            Some((quotient, _)) => quotient,
+            None => operation(Operation::Divide(a, b), interval),
+        },
+        _ => operation(Operation::Divide(a, b), interval),
+    })
+}
+
+fn modulo(a: Rc<Expression>, b: Rc<Expression>, index: usize) -> Result<Rc<Expression>, AluError> {
+    let interval = a
+        .interval
+        .modulo(&b.interval)
+        .ok_or(AluError::InvalidModulo(index))?;
+
+    Ok(match (a.as_constant(), b.as_constant()) {
+        (Some(a), Some(b)) => constant(a % b),
+        (_, Some(m)) if a.interval.is_within(&Interval::new(0, m - 1)) => a,
+        (_, Some(m)) => match split_multiple(&a, m, index) {
+            Some((_, remainder)) => remainder,
+            None => operation(Operation::Modulo(a, b), interval),
+        },
+        _ => operation(Operation::Modulo(a, b), interval),
+    })
+}
+
+fn equal(a: Rc<Expression>, b: Rc<Expression>) -> Rc<Expression> {
+    if Rc::ptr_eq(&a, &b) {
+        return constant(1);
+    }
+
+    match (a.as_constant(), b.as_constant(), &a.operation) {
+        (Some(_), None, _) => equal(b, a),
+        // Comparing a comparison with 0 or 1 negates it or leaves it be
+        (_, Some(0), Operation::Equal(x, y)) => not_equal(x.clone(), y.clone()),
+        (_, Some(0), Operation::NotEqual(x, y)) => equal(x.clone(), y.clone()),
+        (_, Some(1), Operation::Equal(_, _) | Operation::NotEqual(_, _)) => a,
+        _ => {
+            let interval = a.interval.equal(&b.interval);
+            operation(Operation::Equal(a, b), interval)
+        }
+    }
+}
+
+fn not_equal(a: Rc<Expression>, b: Rc<Expression>) -> Rc<Expression> {
+    let interval = match a.interval.equal(&b.interval).as_constant() {
+        Some(value) => Interval::constant(1 - value),
+        None => Interval::new(0, 1),
+    };
+
+    operation(Operation::NotEqual(a, b), interval)
+}
+
+// What an ALU program computes, written in terms of its input digits. Whenever the program reads a
+// digit (and when it finishes), any registers holding something more complicated than a constant,
+// digit or variable are named after the register and the number of the digit last read (`z3` is
+// `z` just after the program's done with d3).
+pub struct SymbolicProgram {
+    registers: [Rc<Expression>; 4],
+    variables: Vec<Rc<Expression>>,
+}
+
+impl SymbolicProgram {
+    pub fn from_program(alu: &ArithmeticLogicUnit) -> Result<Self, AluError> {
+        let mut registers = [constant(0), constant(0), constant(0), constant(0)];
+        let mut variables = Vec::new();
+        let mut digits_read = 0;
+
+        for (index, instruction) in alu.instructions().iter().enumerate() {
+            let value = |value: &Value, registers: &[Rc<Expression>; 4]| match value {
+                Value::Literal(literal) => constant(*literal),
+                Value::Register(register) => registers[register.index()].clone(),
+            };
+
+            match instruction {
+                Instruction::Input(register) => {
+                    for (named, variable) in name_registers(&registers, digits_read) {
+                        variables.push(variable.clone());
+                        registers[named.index()] = variable;
+                    }
+
+                    registers[register.index()] = digit(digits_read);
+                    digits_read += 1;
+                }
+                Instruction::Add(register, b) => {
+                    let a = registers[register.index()].clone();
+                    registers[register.index()] = add(a, value(b, &registers), index)?;
+                }
+                Instruction::Multiply(register, b) => {
+                    let a = registers[register.index()].clone();
+                    registers[register.index()] = multiply(a, value(b, &registers), index)?;
+                }
+                Instruction::Divide(register, b) => {
+                    let a = registers[register.index()].clone();
+                    registers[register.index()] = divide(a, value(b, &registers), index)?;
+                }
+                Instruction::Modulo(register, b) => {
+                    let a = registers[register.index()].clone();
+                    registers[register.index()] = modulo(a, value(b, &registers), index)?;
+                }
+                Instruction::Compare(register, b) => {
+                    let a = registers[register.index()].clone();
+                    registers[register.index()] = equal(a, value(b, &registers));
+                }
+            }
+        }
+
+        // Naming what's left lets `describe` refer to shared parts of the final values, but the
+        // registers themselves stay as they are
+        variables.extend(
+            name_registers(&registers, digits_read)
+                .into_iter()
+                .map(|(_, variable)| variable),
+        );
+
+        Ok(SymbolicProgram {
+            registers,
+            variables,
+        })
+    }
+
+    pub fn register(&self, register: Register) -> &Rc<Expression> {
+        &self.registers[register.index()]
+    }
+
+    // Writes out the final value of a register, preceded by the definition of each variable it
+    // depends on, one per line in the order the program computes them. A sub-expression that
+    // appears more than once in a line is replaced by a variable if one names it.
+    pub fn describe(&self, register: Register) -> String {
+        let names: HashMap<*const Expression, &Expression> = self
+            .variables
+            .iter()
+            .filter_map(|variable| match &variable.operation {
+                Operation::Variable(_, expression) => Some((Rc::as_ptr(expression), &**variable)),
+                _ => None,
+            })
+            .collect();
+
+        let mut pending = Vec::new();
+        let final_line = describe_line(self.register(register), &names, &mut pending);
+        let mut definitions = HashMap::new();
+
+        while let Some(variable) = pending.pop() {
+            if let Operation::Variable(name, expression) = &variable.operation {
+                if let Entry::Vacant(entry) = definitions.entry(variable as *const Expression) {
+                    let line = describe_line(expression, &names, &mut pending);
+                    entry.insert(format!("{} = {}", name, line));
+                }
+            }
+        }
+
+        self.variables
+            .iter()
+            .filter_map(|variable| definitions.remove(&Rc::as_ptr(variable)))
+            .chain(std::iter::once(format!("{} = {}", register, final_line)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn name_registers(
+    registers: &[Rc<Expression>; 4],
+    digits_read: usize,
+) -> Vec<(Register, Rc<Expression>)> {
+    if digits_read == 0 {
+        return Vec::new();
+    }
+
+    Register::ALL
+        .into_iter()
+        .filter(|register| !registers[register.index()].is_trivial())
+        .map(|register| {
+            let name = format!("{}{}", register, digits_read - 1);
+            (register, variable(name, registers[register.index()].clone()))
+        })
+        .collect()
+}
+
+fn describe_line<'a>(
+    expression: &'a Expression,
+    names: &HashMap<*const Expression, &'a Expression>,
+    referenced: &mut Vec<&'a Expression>,
+) -> String {
+    let mut counts = HashMap::new();
+    count_references(expression, &mut counts);
+
+    let shared: HashMap<_, _> = counts
+        .into_iter()
+        .filter(|&(pointer, count)| count > 1 && !std::ptr::eq(pointer, expression))
+        .filter_map(|(pointer, _)| names.get(&pointer).map(|&variable| (pointer, variable)))
+        .collect();
+
+    let mut line = String::new();
+    render(expression, &shared, 0, &mut line, referenced);
+    line
+}
+
+// Counts how often each sub-expression appears in an expression, without looking inside variables
+fn count_references(expression: &Expression, counts: &mut HashMap<*const Expression, usize>) {
+    let count = counts.entry(expression as *const Expression).or_insert(0);
+    *count += 1;
+
+    if *count == 1 {
+        if let Some((a, b)) = expression.operands() {
+            count_references(a, counts);
+            count_references(b, counts);
+        }
+    }
+}
+
+fn render<'a>(
+    expression: &'a Expression,
+    shared: &HashMap<*const Expression, &'a Expression>,
+    parent_precedence: u8,
+    out: &mut String,
+    referenced: &mut Vec<&'a Expression>,
+) {
+    if let Some(&variable) = shared.get(&(expression as *const Expression)) {
+        return render(variable, shared, parent_precedence, out, referenced);
+    }
+
+    let precedence = expression.precedence();
+    let parenthesize = precedence < parent_precedence;
+
+    if parenthesize {
+        out.push('(');
+    }
+
+    let mut binary = |a: &'a Rc<Expression>, symbol: &str, b: &'a Rc<Expression>| {
+        // Addition and multiplication chain without parentheses; everything else groups to the left
+        let right_precedence = match (&expression.operation, &b.operation) {
+            (Operation::Add(_, _), Operation::Add(_, _))
+            | (Operation::Multiply(_, _), Operation::Multiply(_, _)) => precedence,
+            _ => precedence + 1,
+        };
+
+        render(a, shared, precedence, out, referenced);
+        out.push_str(symbol);
+        render(b, shared, right_precedence, out, referenced);
+    };
+
+    match &expression.operation {
+        Operation::Constant(value) => out.push_str(&value.to_string()),
+        Operation::Digit(index) => out.push_str(&format!("d{}", index)),
+        Operation::Variable(name, _) => {
+            out.push_str(name);
+            referenced.push(expression);
+        }
+        Operation::Add(a, b) => match b.as_constant() {
+            Some(value) if value < 0 => {
+                render(a, shared, precedence, out, referenced);
+                out.push_str(&format!(" - {}", -value));
+            }
+            _ => binary(a, " + ", b),
+        },
+        Operation::Multiply(a, b) => binary(a, " * ", b),
+        Operation::Divide(a, b) => binary(a, " / ", b),
+        Operation::Modulo(a, b) => binary(a, " % ", b),
+        Operation::Equal(a, b) => binary(a, " == ", b),
+        Operation::NotEqual(a, b) => binary(a, " != ", b),
+    }
+
+    if parenthesize {
+        out.push(')');
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut out = String::new();
+        render(self, &HashMap::new(), 0, &mut out, &mut Vec::new());
+
+        write!(f, "{}", out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_INSTRUCTIONS: &str = include_str!("../../data/day24.txt");
+
+    fn compile(program: &str) -> Result<SymbolicProgram, AluError> {
+        SymbolicProgram::from_program(&program.parse().unwrap())
+    }
+
+    #[test]
+    fn test_simplify() {
+        let program = compile(indoc! {"
+            inp w
+            add x 3
+            mul x 2
+            eql x w
+            add y 12
+            eql y w
+            eql y 0
+            add z w
+            add z 4
+            add z -5
+            mod z 26
+        "})
+        .unwrap();
+
+        assert_eq!("d0 == 6", program.register(Register::X).to_string());
+        assert_eq!(Some(1), program.register(Register::Y).as_constant());
+        assert_eq!("d0 - 1", program.register(Register::Z).to_string());
+        assert_eq!(Interval::new(0, 8), program.register(Register::Z).interval());
+
+        // Once digits are "pushed" onto z, dividing by the same base pops them back off
+        let program = compile(indoc! {"
+            inp w
+            add z w
+            inp w
+            mul z 26
+            add z w
+            add z 7
+            inp w
+            add x z
+            mod x 26
+            div z 26
+        "})
+        .unwrap();
+
+        assert_eq!("d1 + 7", program.register(Register::X).to_string());
+        assert_eq!("d0", program.register(Register::Z).to_string());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Some(AluError::DivisionByZero(1)),
+            compile("inp w\ndiv w 0").err()
+        );
+        assert_eq!(
+            Some(AluError::InvalidModulo(2)),
+            compile("inp w\nmul w -1\nmod w 5").err()
+        );
+
+        // Constant arithmetic overflows just like it does in the ALU
+        assert_eq!(
+            Some(AluError::Overflow(1)),
+            compile("add x 9223372036854775807\nadd x 1").err()
+        );
+        assert_eq!(
+            Some(AluError::Overflow(1)),
+            compile("add x 4611686018427387904\nmul x 2").err()
+        );
+        assert_eq!(
+            Some(AluError::Overflow(2)),
+            compile("add x -9223372036854775807\nadd x -1\ndiv x -1").err()
+        );
+
+        // ...but gathering up constants that the ALU adds one at a time isn't an overflow
+        let alu = "inp w\nmul w -1\nadd w 9223372036854775807\nadd w 1";
+        let program = compile(alu).unwrap();
+
+        assert_eq!(
+            alu.parse::<ArithmeticLogicUnit>().unwrap().execute(&[3]).unwrap()[0],
+            program.register(Register::W).evaluate(&[3])
+        );
+    }
+
+    #[test]
+    fn test_describe_monad() {
+        let alu: ArithmeticLogicUnit = TEST_INSTRUCTIONS.parse().unwrap();
+        let program = SymbolicProgram::from_program(&alu).unwrap();
+
+        let expected = indoc! {"
+            z0 = d0 + 6
+            z1 = z0 * 26 + d1 + 14
+            x3 = d2 + 6 != d3
+            z3 = z1 * (x3 * 25 + 1) + (d3 + 10) * x3
+            z4 = z3 * 26 + d4 + 9
+            x6 = d5 + 1 != d6
+            z6 = z4 * (x6 * 25 + 1) + (d6 + 8) * x6
+            x7 = z6 % 26 - 4 != d7
+            z7 = z6 / 26 * (x7 * 25 + 1) + (d7 + 13) * x7
+            x8 = z7 % 26 - 15 != d8
+            z8 = z7 / 26 * (x8 * 25 + 1) + (d8 + 12) * x8
+            z9 = z8 * 26 + d9 + 6
+            x11 = d10 + 8 != d11
+            z11 = z9 * (x11 * 25 + 1) + (d11 + 15) * x11
+            x12 = z11 % 26 - 8 != d12
+            z12 = z11 / 26 * (x12 * 25 + 1) + (d12 + 4) * x12
+            x13 = z12 % 26 - 14 != d13
+            z = z12 / 26 * (x13 * 25 + 1) + (d13 + 10) * x13"};
+
+        assert_eq!(expected, program.describe(Register::Z));
+        assert_eq!(0, program.register(Register::Z).interval().min);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let alu: ArithmeticLogicUnit = TEST_INSTRUCTIONS.parse().unwrap();
+        let program = SymbolicProgram::from_program(&alu).unwrap();
+
+        for model_number in [99394899891971u64, 92171126131911, 13579246899999, 11111111111111] {
+            let digits: Vec<i64> = model_number
+                .to_string()
+                .chars()
+                .map(|c| c.to_digit(10).unwrap() as i64)
+                .collect();
+
+            let registers = alu.execute(&digits).unwrap();
+
+            for register in Register::ALL {
+                assert_eq!(
+                    registers[register.index()],
+                    program.register(register).evaluate(&digits)
+                );
+            }
+        }
+    }
+}
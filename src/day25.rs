@@ -1,23 +1,13 @@
+use crate::grid::Grid;
+use crate::parse_error::ParseError;
+use crate::solution::{Input, Solution};
 use self::Space::*;
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
-use std::{env, error};
-
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if let Some(path) = args.get(1) {
-        let map = SeaCucumberMap::from_str(std::fs::read_to_string(path)?.as_str())?;
-
-        println!("Time to settle: {} steps", map.time_to_settle());
-
-        Ok(())
-    } else {
-        Err("Usage: day25 INPUT_FILE_PATH".into())
-    }
-}
+use std::error;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-enum Space {
+pub enum Space {
     Empty,
     East,
     South,
@@ -37,8 +27,8 @@ impl FromStr for Space {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-struct SeaCucumberMap {
-    spaces: Vec<Vec<Space>>,
+pub struct SeaCucumberMap {
+    spaces: Grid<Space>,
 }
 
 impl SeaCucumberMap {
@@ -61,64 +51,70 @@ impl SeaCucumberMap {
     fn next(&self) -> Self {
         let mut next_spaces = self.spaces.clone();
 
-        let width = next_spaces[0].len();
-        let height = next_spaces.len();
-
-        {
-            let mut east_movers = Vec::new();
-
-            for row in 0..height {
-                for col in 0..width {
-                    if next_spaces[row][col] == East && next_spaces[row][(col + 1) % width] == Empty
-                    {
-                        east_movers.push((row, col));
-                    }
-                }
-            }
-
-            for (row, col) in east_movers {
-                next_spaces[row][col] = Empty;
-                next_spaces[row][(col + 1) % width] = East;
-            }
-        }
-
-        {
-            let mut south_movers = Vec::new();
-
-            for row in 0..height {
-                for col in 0..width {
-                    if next_spaces[row][col] == South
-                        && next_spaces[(row + 1) % height][col] == Empty
-                    {
-                        south_movers.push((row, col));
-                    }
-                }
-            }
-
-            for (row, col) in south_movers {
-                next_spaces[row][col] = Empty;
-                next_spaces[(row + 1) % height][col] = South;
-            }
-        }
+        Self::move_herd(&mut next_spaces, East, (0, 1));
+        Self::move_herd(&mut next_spaces, South, (1, 0));
 
         SeaCucumberMap {
             spaces: next_spaces,
         }
     }
+
+    // Moves every sea cucumber in the given herd that has an empty space in front of it; all
+    // members of the herd decide whether to move before any of them actually moves
+    fn move_herd(spaces: &mut Grid<Space>, herd: Space, offset: (isize, isize)) {
+        let movers: Vec<_> = spaces
+            .enumerate()
+            .filter(|&(_, &space)| space == herd)
+            .map(|(position, _)| (position, spaces.wrapping_offset(position, offset)))
+            .filter(|&(_, destination)| spaces[destination] == Empty)
+            .collect();
+
+        for (position, destination) in movers {
+            spaces[position] = Empty;
+            spaces[destination] = herd;
+        }
+    }
 }
 
 impl FromStr for SeaCucumberMap {
-    type Err = Box<dyn error::Error>;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| Space::from_str(c.to_string().as_str()))
-                    .collect::<Result<Vec<Space>, Box<dyn error::Error>>>()
-            })
-            .collect::<Result<Vec<Vec<Space>>, Box<dyn error::Error>>>()
-            .map(|spaces| SeaCucumberMap { spaces })
+        let spaces = Grid::parse(25, s, "'.', '>' or 'v'", |c| {
+            Space::from_str(c.to_string().as_str()).ok()
+        })?;
+
+        Ok(SeaCucumberMap { spaces })
+    }
+}
+
+impl Display for SeaCucumberMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.spaces.render(|space| match space {
+            Empty => '.',
+            East => '>',
+            South => 'v',
+        }))
+    }
+}
+
+pub struct Day25 {
+    map: SeaCucumberMap,
+}
+
+impl Solution for Day25 {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Day25 {
+            map: SeaCucumberMap::from_str(input.text()?)?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>> {
+        Ok(self.map.time_to_settle().to_string())
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>> {
+        Err("Day 25 has no second part".into())
     }
 }
 
@@ -136,14 +132,15 @@ mod test {
         "};
 
         let expected = SeaCucumberMap {
-            spaces: vec![
-                vec![Empty, Empty, Empty],
-                vec![Empty, East, Empty],
-                vec![Empty, Empty, South],
-            ],
+            spaces: Grid::from_rows([
+                [Empty, Empty, Empty],
+                [Empty, East, Empty],
+                [Empty, Empty, South],
+            ]),
         };
 
         assert_eq!(expected, SeaCucumberMap::from_str(map_string).unwrap());
+        assert_eq!(map_string, expected.to_string());
     }
 
     #[test]
@@ -0,0 +1,370 @@
+use crate::parse_error::ParseError;
+use std::ops::{Index, IndexMut};
+
+pub type RowCol = (usize, usize);
+
+// Describes which cells count as a cell's neighbors: the four orthogonally-adjacent cells (von
+// Neumann) or all eight surrounding cells (Moore), optionally wrapping around the edges of the grid
+// as if it were a torus.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Neighborhood {
+    offsets: &'static [(isize, isize)],
+    toroidal: bool,
+}
+
+impl Neighborhood {
+    pub const VON_NEUMANN: Neighborhood = Neighborhood {
+        offsets: &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+        toroidal: false,
+    };
+
+    pub const MOORE: Neighborhood = Neighborhood {
+        offsets: &[
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+        ],
+        toroidal: false,
+    };
+
+    pub const fn toroidal(self) -> Self {
+        Neighborhood {
+            toroidal: true,
+            ..self
+        }
+    }
+
+    // Returns the neighbors of the given position within a grid of `bounds` rows and columns. This
+    // doesn't need an actual grid, which is handy for grids that are too big (or too repetitive) to
+    // bother storing.
+    pub fn neighbors(&self, position: RowCol, bounds: RowCol) -> impl Iterator<Item = RowCol> {
+        let toroidal = self.toroidal;
+
+        self.offsets
+            .iter()
+            .filter_map(move |&offset| offset_position(position, offset, bounds, toroidal))
+    }
+}
+
+// A rectangular grid of cells stored row by row in a single buffer and addressed by (row, column)
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "Cell count must match grid size");
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(RowCol) -> T) -> Self {
+        let cells = (0..width * height)
+            .map(|i| f((i / width, i % width)))
+            .collect();
+
+        Grid::new(width, height, cells)
+    }
+
+    // Builds a grid from nested rows; panics if the rows aren't all the same length
+    pub fn from_rows<R: IntoIterator<Item = T>>(rows: impl IntoIterator<Item = R>) -> Self {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for row in rows {
+            let len_before = cells.len();
+            cells.extend(row);
+
+            let row_width = cells.len() - len_before;
+            assert_eq!(*width.get_or_insert(row_width), row_width, "Rows must have equal lengths");
+
+            height += 1;
+        }
+
+        Grid::new(width.unwrap_or(0), height, cells)
+    }
+
+    // Parses a grid in which each character represents one cell; `expected` describes a valid cell
+    // for error messages
+    pub fn parse(
+        day: u32,
+        string: &str,
+        expected: &str,
+        parse_cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (row, line) in string.lines().enumerate() {
+            for (i, c) in line.char_indices() {
+                let cell = parse_cell(c).ok_or_else(|| {
+                    ParseError::at(day, line, &line[i..], expected).on_line(row + 1)
+                })?;
+
+                cells.push(cell);
+            }
+
+            let row_width = line.chars().count();
+
+            if row_width != *width.get_or_insert(row_width) {
+                return Err(ParseError::at_end(
+                    day,
+                    line,
+                    format!("a row of {} cells", width.unwrap()),
+                )
+                .on_line(row + 1));
+            }
+
+            height += 1;
+        }
+
+        if cells.is_empty() {
+            return Err(ParseError::new(day, 1, 1, expected));
+        }
+
+        Ok(Grid::new(width.unwrap(), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounds(&self) -> RowCol {
+        (self.height, self.width)
+    }
+
+    pub fn contains(&self, (row, col): RowCol) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, position: RowCol) -> Option<&T> {
+        if self.contains(position) {
+            Some(&self.cells[self.cell_index(position)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, position: RowCol) -> Option<&mut T> {
+        if self.contains(position) {
+            let index = self.cell_index(position);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = RowCol> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i / width, i % width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = (RowCol, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "Column out of bounds");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |col| self.column(col))
+    }
+
+    pub fn neighbors(
+        &self,
+        position: RowCol,
+        neighborhood: Neighborhood,
+    ) -> impl Iterator<Item = RowCol> {
+        neighborhood.neighbors(position, self.bounds())
+    }
+
+    // Moves from the given position by the given offset, wrapping around the edges of the grid
+    pub fn wrapping_offset(&self, position: RowCol, offset: (isize, isize)) -> RowCol {
+        offset_position(position, offset, self.bounds(), true).unwrap()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    pub fn render(&self, render_cell: impl Fn(&T) -> char) -> String {
+        let mut string = String::with_capacity((self.width + 1) * self.height);
+
+        for row in self.rows() {
+            string.extend(row.iter().map(&render_cell));
+            string.push('\n');
+        }
+
+        string
+    }
+
+    fn cell_index(&self, (row, col): RowCol) -> usize {
+        (row * self.width) + col
+    }
+}
+
+impl<T> Index<RowCol> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: RowCol) -> &Self::Output {
+        assert!(self.contains(position), "Position out of bounds");
+        &self.cells[self.cell_index(position)]
+    }
+}
+
+impl<T> IndexMut<RowCol> for Grid<T> {
+    fn index_mut(&mut self, position: RowCol) -> &mut Self::Output {
+        assert!(self.contains(position), "Position out of bounds");
+        let index = self.cell_index(position);
+
+        &mut self.cells[index]
+    }
+}
+
+fn offset_position(
+    (row, col): RowCol,
+    (d_row, d_col): (isize, isize),
+    (rows, cols): RowCol,
+    toroidal: bool,
+) -> Option<RowCol> {
+    let row = row as isize + d_row;
+    let col = col as isize + d_col;
+
+    if toroidal {
+        Some((
+            row.rem_euclid(rows as isize) as usize,
+            col.rem_euclid(cols as isize) as usize,
+        ))
+    } else if row >= 0 && col >= 0 && (row as usize) < rows && (col as usize) < cols {
+        Some((row as usize, col as usize))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_GRID_STRING: &str = indoc! {"
+        123
+        456
+    "};
+
+    fn test_grid() -> Grid<u32> {
+        Grid::parse(0, TEST_GRID_STRING, "a digit", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Grid::from_rows([[1, 2, 3], [4, 5, 6]]), test_grid());
+
+        assert_eq!(
+            Err(ParseError::new(0, 2, 2, "a digit")),
+            Grid::parse(0, "123\n4x6\n", "a digit", |c| c.to_digit(10))
+        );
+
+        assert_eq!(
+            Err(ParseError::new(0, 2, 3, "a row of 3 cells")),
+            Grid::parse(0, "123\n45\n", "a digit", |c| c.to_digit(10))
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            TEST_GRID_STRING,
+            test_grid().render(|&d| char::from_digit(d, 10).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_index() {
+        let mut grid = test_grid();
+
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(6, grid[(1, 2)]);
+        assert_eq!(None, grid.get((2, 0)));
+        assert_eq!(None, grid.get((0, 3)));
+
+        grid[(1, 2)] = 9;
+        assert_eq!(Some(&9), grid.get((1, 2)));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = test_grid();
+
+        assert_eq!(vec![&[1, 2, 3][..], &[4, 5, 6][..]], grid.rows().collect::<Vec<_>>());
+        assert_eq!(&[4, 5, 6], grid.row(1));
+
+        assert_eq!(
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]],
+            grid.columns()
+                .map(|column| column.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::from_fn(3, 3, |(row, col)| row * 3 + col);
+
+        let neighbors =
+            |position, neighborhood| grid.neighbors(position, neighborhood).collect::<Vec<_>>();
+
+        assert_eq!(vec![(1, 0), (0, 1)], neighbors((0, 0), Neighborhood::VON_NEUMANN));
+        assert_eq!(
+            vec![(0, 1), (2, 1), (1, 0), (1, 2)],
+            neighbors((1, 1), Neighborhood::VON_NEUMANN)
+        );
+        assert_eq!(vec![(0, 1), (1, 0), (1, 1)], neighbors((0, 0), Neighborhood::MOORE));
+        assert_eq!(8, neighbors((1, 1), Neighborhood::MOORE).len());
+
+        assert_eq!(
+            vec![(2, 0), (1, 0), (0, 2), (0, 1)],
+            neighbors((0, 0), Neighborhood::VON_NEUMANN.toroidal())
+        );
+        assert_eq!(8, neighbors((0, 0), Neighborhood::MOORE.toroidal()).len());
+
+        assert_eq!((0, 0), grid.wrapping_offset((2, 2), (1, 1)));
+    }
+}
@@ -0,0 +1,131 @@
+use std::error;
+use std::fmt::{Display, Formatter};
+
+// Describes where a puzzle input went wrong and what the parser expected to find there. Lines and
+// columns count from 1. Parsers that only ever see a single line report line 1 and leave it to
+// their callers to point the error at the right line with `on_line`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(day: u32, line: usize, column: usize, expected: impl Into<String>) -> Self {
+        ParseError {
+            day,
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    // Builds an error pointing at the start of `token`, which must be a slice of `line`
+    pub fn at(day: u32, line: &str, token: &str, expected: impl Into<String>) -> Self {
+        ParseError::new(day, 1, column_of(line, token), expected)
+    }
+
+    // Builds an error pointing just past the end of `line` for input that stopped too early
+    pub fn at_end(day: u32, line: &str, expected: impl Into<String>) -> Self {
+        ParseError::new(day, 1, line.len() + 1, expected)
+    }
+
+    pub fn on_line(self, line: usize) -> Self {
+        ParseError { line, ..self }
+    }
+
+    // Moves an error produced while parsing `token` on its own so it points at the same place
+    // within `line`, of which `token` must be a slice
+    pub fn within(self, line: &str, token: &str) -> Self {
+        ParseError {
+            column: self.column + column_of(line, token) - 1,
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Day {}, line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+// Parses each line of `text` with the given function, moving any error to the line on which it
+// happened
+pub fn parse_lines<T>(
+    text: &str,
+    parse: impl Fn(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|error| error.on_line(i + 1)))
+        .collect()
+}
+
+fn column_of(line: &str, token: &str) -> usize {
+    let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+    debug_assert!(offset <= line.len(), "Token must be a slice of the line");
+
+    offset + 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        let line = "forward x";
+
+        assert_eq!(
+            ParseError::new(2, 1, 9, "a magnitude"),
+            ParseError::at(2, line, &line[8..], "a magnitude")
+        );
+
+        assert_eq!(
+            ParseError::new(2, 1, 10, "a magnitude"),
+            ParseError::at_end(2, line, "a magnitude")
+        );
+    }
+
+    #[test]
+    fn test_within() {
+        let line = "1,2 -> 3,x";
+        let token = &line[7..];
+
+        assert_eq!(
+            ParseError::new(5, 1, 10, "a coordinate"),
+            ParseError::at(5, token, &token[2..], "a coordinate").within(line, token)
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let parse = |line: &str| {
+            line.parse::<u32>()
+                .map_err(|_| ParseError::at(1, line, line, "a number"))
+        };
+
+        assert_eq!(Ok(vec![1, 2, 3]), parse_lines("1\n2\n3\n", parse));
+        assert_eq!(
+            Err(ParseError::new(1, 2, 1, "a number")),
+            parse_lines("1\nx\n3\n", parse)
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "Day 15, line 3, column 7: expected a risk score digit",
+            ParseError::new(15, 3, 7, "a risk score digit").to_string()
+        );
+    }
+}
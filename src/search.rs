@@ -0,0 +1,387 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+// A node in a search space. States that need outside information to find their successors (a map,
+// a graph) can carry a reference to it.
+pub trait State: Sized {
+    // Returns each state reachable in a single step from this one along with the cost of that step
+    fn successors(&self) -> Vec<(Self, u32)>;
+
+    fn is_goal(&self) -> bool;
+
+    // Estimates the cost of the cheapest path from this state to a goal. A* only finds the cheapest
+    // path if this never overestimates; the default of 0 turns A* into Dijkstra's algorithm.
+    fn heuristic(&self) -> u32 {
+        0
+    }
+}
+
+// A path from the start state to a goal, including both ends, along with its total cost
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Path<S> {
+    pub states: Vec<S>,
+    pub cost: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SearchResult<S> {
+    pub path: Option<Path<S>>,
+
+    // The number of states whose successors were generated before the search finished
+    pub expanded: usize,
+}
+
+// A search space that's explored by changing a single state in place: each step is taken on the
+// way down and undone on the way back up, so an exhaustive search never copies the whole state
+// (a path, say) for every step
+pub trait Backtrack {
+    type Step: Copy;
+
+    // Returns each step that can be taken from the current state
+    fn steps(&self) -> Vec<Self::Step>;
+
+    fn take(&mut self, step: Self::Step);
+
+    // Reverses a step that was just taken, restoring the state from before it
+    fn undo(&mut self, step: Self::Step);
+
+    fn is_goal(&self) -> bool;
+}
+
+// Finds the cheapest path from the given state to a goal
+pub fn dijkstra<S: State + Clone + Eq + Hash>(start: S) -> SearchResult<S> {
+    best_first(start, |_| 0)
+}
+
+// Finds the cheapest path from the given state to a goal, expanding the states that look closest to
+// a goal (according to `State::heuristic`) first
+pub fn a_star<S: State + Clone + Eq + Hash>(start: S) -> SearchResult<S> {
+    best_first(start, S::heuristic)
+}
+
+// Finds the path with the fewest steps from the given state to a goal, regardless of step costs
+pub fn breadth_first<S: State + Clone + Eq + Hash>(start: S) -> SearchResult<S> {
+    let mut states = vec![start.clone()];
+    let mut visited = HashMap::from([(start, Visit::root())]);
+    let mut queue = VecDeque::from([0]);
+    let mut expanded = 0;
+
+    while let Some(index) = queue.pop_front() {
+        if states[index].is_goal() {
+            return SearchResult {
+                path: Some(reconstruct_path(&states, &visited, index)),
+                expanded,
+            };
+        }
+
+        expanded += 1;
+        let cost = visited_cost(&visited, &states[index]);
+
+        for (successor, step_cost) in states[index].successors() {
+            if let Entry::Vacant(entry) = visited.entry(successor.clone()) {
+                entry.insert(Visit {
+                    index: states.len(),
+                    parent: Some(index),
+                    cost: cost + step_cost,
+                    closed: true,
+                });
+
+                queue.push_back(states.len());
+                states.push(successor);
+            }
+        }
+    }
+
+    SearchResult {
+        path: None,
+        expanded,
+    }
+}
+
+// Passes every goal reachable from the given state to `visit_goal`, leaving the state as it was
+// afterward, and returns the number of states whose steps were generated. Goals are not expanded
+// any further. The search keeps no record of visited states, so the state graph must be acyclic
+// (for example, by having the state remember the path that led to it).
+pub fn depth_first_goals<B: Backtrack>(state: &mut B, mut visit_goal: impl FnMut(&B)) -> usize {
+    if state.is_goal() {
+        visit_goal(state);
+        return 0;
+    }
+
+    // The steps left to try at each level, and the steps taken to get to the deepest one
+    let mut pending = vec![state.steps().into_iter()];
+    let mut taken = Vec::new();
+    let mut expanded = 1;
+
+    while let Some(steps) = pending.last_mut() {
+        match steps.next() {
+            Some(step) => {
+                state.take(step);
+
+                if state.is_goal() {
+                    visit_goal(state);
+                    state.undo(step);
+                } else {
+                    expanded += 1;
+                    taken.push(step);
+                    pending.push(state.steps().into_iter());
+                }
+            }
+            None => {
+                pending.pop();
+
+                if let Some(step) = taken.pop() {
+                    state.undo(step);
+                }
+            }
+        }
+    }
+
+    expanded
+}
+
+// Bookkeeping for a state the search has seen: where it lives in the state list, how we got there
+// and the cheapest known cost of getting there
+struct Visit {
+    index: usize,
+    parent: Option<usize>,
+    cost: u32,
+    closed: bool,
+}
+
+impl Visit {
+    fn root() -> Self {
+        Visit {
+            index: 0,
+            parent: None,
+            cost: 0,
+            closed: false,
+        }
+    }
+}
+
+#[derive(Eq, PartialEq)]
+struct QueueEntry {
+    priority: u32,
+    cost: u32,
+    index: usize,
+}
+
+impl Ord for QueueEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Swap the "normal" order so we have a min-first heap
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| other.cost.cmp(&self.cost))
+            .then_with(|| self.index.cmp(&other.index))
+    }
+}
+
+impl PartialOrd for QueueEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn best_first<S: State + Clone + Eq + Hash>(
+    start: S,
+    heuristic: impl Fn(&S) -> u32,
+) -> SearchResult<S> {
+    let mut queue = BinaryHeap::from([QueueEntry {
+        priority: heuristic(&start),
+        cost: 0,
+        index: 0,
+    }]);
+
+    let mut states = vec![start.clone()];
+    let mut visited = HashMap::from([(start, Visit::root())]);
+    let mut expanded = 0;
+
+    while let Some(QueueEntry { cost, index, .. }) = queue.pop() {
+        let visit = visited.get_mut(&states[index]).unwrap();
+
+        // Skip stale queue entries for states we've since reached more cheaply (or have already
+        // expanded)
+        if visit.closed || cost > visit.cost {
+            continue;
+        }
+
+        visit.closed = true;
+
+        if states[index].is_goal() {
+            return SearchResult {
+                path: Some(reconstruct_path(&states, &visited, index)),
+                expanded,
+            };
+        }
+
+        expanded += 1;
+
+        for (successor, step_cost) in states[index].successors() {
+            let successor_cost = cost + step_cost;
+
+            let successor_index = match visited.entry(successor) {
+                Entry::Occupied(mut entry) => {
+                    let visit = entry.get_mut();
+
+                    if visit.closed || visit.cost <= successor_cost {
+                        continue;
+                    }
+
+                    visit.cost = successor_cost;
+                    visit.parent = Some(index);
+                    visit.index
+                }
+                Entry::Vacant(entry) => {
+                    states.push(entry.key().clone());
+
+                    entry.insert(Visit {
+                        index: states.len() - 1,
+                        parent: Some(index),
+                        cost: successor_cost,
+                        closed: false,
+                    });
+
+                    states.len() - 1
+                }
+            };
+
+            queue.push(QueueEntry {
+                priority: successor_cost + heuristic(&states[successor_index]),
+                cost: successor_cost,
+                index: successor_index,
+            });
+        }
+    }
+
+    SearchResult {
+        path: None,
+        expanded,
+    }
+}
+
+fn visited_cost<S: Eq + Hash>(visited: &HashMap<S, Visit>, state: &S) -> u32 {
+    visited[state].cost
+}
+
+fn reconstruct_path<S: Clone + Eq + Hash>(
+    states: &[S],
+    visited: &HashMap<S, Visit>,
+    goal: usize,
+) -> Path<S> {
+    let cost = visited_cost(visited, &states[goal]);
+    let mut path = vec![states[goal].clone()];
+    let mut current = goal;
+
+    while let Some(parent) = visited[&states[current]].parent {
+        path.push(states[parent].clone());
+        current = parent;
+    }
+
+    path.reverse();
+
+    Path { states: path, cost }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // A walk along a number line toward 10, where a big step costs more than two small ones
+    #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+    struct Walk(u32);
+
+    impl State for Walk {
+        fn successors(&self) -> Vec<(Self, u32)> {
+            vec![(Walk(self.0 + 1), 1), (Walk(self.0 + 2), 3)]
+                .into_iter()
+                .filter(|(walk, _)| walk.0 <= 10)
+                .collect()
+        }
+
+        fn is_goal(&self) -> bool {
+            self.0 == 10
+        }
+
+        fn heuristic(&self) -> u32 {
+            10 - self.0
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let result = dijkstra(Walk(0));
+        let path = result.path.unwrap();
+
+        assert_eq!(10, path.cost);
+        assert_eq!((0..=10).map(Walk).collect::<Vec<_>>(), path.states);
+        assert!(result.expanded >= 10);
+    }
+
+    #[test]
+    fn test_a_star() {
+        let result = a_star(Walk(0));
+
+        assert_eq!(10, result.path.unwrap().cost);
+        assert!(result.expanded <= dijkstra(Walk(0)).expanded);
+    }
+
+    #[test]
+    fn test_breadth_first() {
+        let path = breadth_first(Walk(0)).path.unwrap();
+
+        assert_eq!(6, path.states.len());
+        assert_eq!(15, path.cost);
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        let result = dijkstra(Walk(11));
+
+        assert_eq!(None, result.path);
+        assert_eq!(1, result.expanded);
+    }
+
+    // The same walk, taken one step at a time
+    impl Backtrack for Walk {
+        type Step = u32;
+
+        fn steps(&self) -> Vec<u32> {
+            [1, 2].into_iter().filter(|step| self.0 + step <= 10).collect()
+        }
+
+        fn take(&mut self, step: u32) {
+            self.0 += step;
+        }
+
+        fn undo(&mut self, step: u32) {
+            self.0 -= step;
+        }
+
+        fn is_goal(&self) -> bool {
+            self.0 == 10
+        }
+    }
+
+    #[test]
+    fn test_depth_first_goals() {
+        // Each goal here is the same state, but reached along a different path; there are as many
+        // ways to climb 10 stairs one or two at a time as the 11th Fibonacci number
+        let mut walk = Walk(0);
+        let mut goals = 0;
+
+        depth_first_goals(&mut walk, |walk| {
+            assert_eq!(10, walk.0);
+            goals += 1;
+        });
+
+        assert_eq!(89, goals);
+        assert_eq!(Walk(0), walk);
+
+        // The start state is already a goal, so there's nothing to expand
+        assert_eq!(0, depth_first_goals(&mut Walk(10), |_| {}));
+    }
+}
@@ -0,0 +1,314 @@
+use crate::*;
+use std::cell::{OnceCell, RefCell};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::error;
+
+// Every day's puzzle has two parts that share a single parsed input. Implementations do all of
+// their parsing up front in `parse` so the two parts can be run (and timed) independently. The
+// exception is days that fold their input line by line as it streams in: those work out both
+// answers in `parse` and say so with `solved_in_parse`, leaving the parts just to report them.
+pub trait Solution {
+    fn parse(input: &Input) -> Result<Self, Box<dyn error::Error>>
+    where
+        Self: Sized;
+
+    fn part1(&self) -> Result<String, Box<dyn error::Error>>;
+
+    fn part2(&self) -> Result<String, Box<dyn error::Error>>;
+
+    fn solved_in_parse(&self) -> bool {
+        false
+    }
+}
+
+// Most days read a single input file, but a few are odd: day 21 takes two starting positions on
+// the command line and day 23 has a separate input file for each part.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum InputKind {
+    File,
+    Files(&'static [&'static str]),
+    Arguments(&'static [&'static str]),
+}
+
+// One of a day's inputs, either already in memory or still waiting to be read from a file or
+// standard input. Streamed sources can be read line by line as they arrive, but only once; reading
+// one all at once keeps its text around for later.
+struct Source {
+    text: OnceCell<String>,
+    reader: RefCell<Option<Box<dyn BufRead>>>,
+}
+
+// The raw values handed to a solution: file (or standard input) contents for file-based days or
+// the literal command-line values for argument-based days.
+pub struct Input {
+    sources: Vec<Source>,
+}
+
+impl Input {
+    pub fn new(values: Vec<String>) -> Self {
+        Input {
+            sources: values
+                .into_iter()
+                .map(|value| Source {
+                    text: OnceCell::from(value),
+                    reader: RefCell::new(None),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn from_readers(readers: Vec<Box<dyn BufRead>>) -> Self {
+        Input {
+            sources: readers
+                .into_iter()
+                .map(|reader| Source {
+                    text: OnceCell::new(),
+                    reader: RefCell::new(Some(reader)),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn text(&self) -> Result<&str, Box<dyn error::Error>> {
+        self.value(0)
+    }
+
+    pub fn value(&self, index: usize) -> Result<&str, Box<dyn error::Error>> {
+        let source = self.source(index)?;
+
+        if source.text.get().is_none() {
+            let mut text = String::new();
+            Input::take_reader(source)?.read_to_string(&mut text)?;
+
+            source.text.set(text).unwrap();
+        }
+
+        Ok(source.text.get().unwrap())
+    }
+
+    // Returns the lines of the given input; streamed input is read one line at a time instead of
+    // all at once
+    pub fn lines(&self, index: usize) -> Result<Lines<'_>, Box<dyn error::Error>> {
+        let source = self.source(index)?;
+
+        match source.text.get() {
+            Some(text) => Ok(Box::new(text.lines().map(|line| Ok(String::from(line))))),
+            None => Ok(Box::new(Input::take_reader(source)?.lines())),
+        }
+    }
+
+    // Reads any streamed sources into memory so this input can be parsed more than once
+    pub fn buffer(&self) -> Result<(), Box<dyn error::Error>> {
+        for index in 0..self.sources.len() {
+            self.value(index)?;
+        }
+
+        Ok(())
+    }
+
+    fn source(&self, index: usize) -> Result<&Source, Box<dyn error::Error>> {
+        self.sources
+            .get(index)
+            .ok_or_else(|| format!("Missing input #{}", index + 1).into())
+    }
+
+    fn take_reader(source: &Source) -> Result<Box<dyn BufRead>, Box<dyn error::Error>> {
+        source
+            .reader
+            .borrow_mut()
+            .take()
+            .ok_or_else(|| "Input has already been read".into())
+    }
+}
+
+pub type Lines<'a> = Box<dyn Iterator<Item = io::Result<String>> + 'a>;
+
+type ParseFunction = fn(&Input) -> Result<Box<dyn Solution>, Box<dyn error::Error>>;
+
+pub struct Day {
+    pub number: u32,
+    pub input_kind: InputKind,
+    parse: ParseFunction,
+}
+
+impl Day {
+    pub fn parse(&self, input: &Input) -> Result<Box<dyn Solution>, Box<dyn error::Error>> {
+        (self.parse)(input)
+    }
+
+    // Returns the conventional input file paths (relative to the given data directory) for this
+    // day, or `None` if this day's input doesn't come from files.
+    pub fn default_paths(&self, data_dir: &str) -> Option<Vec<String>> {
+        match self.input_kind {
+            InputKind::File => Some(vec![format!("{}/day{:02}.txt", data_dir, self.number)]),
+            InputKind::Files(suffixes) => Some(
+                suffixes
+                    .iter()
+                    .map(|suffix| format!("{}/day{:02}-{}.txt", data_dir, self.number, suffix))
+                    .collect(),
+            ),
+            InputKind::Arguments(_) => None,
+        }
+    }
+
+    // Builds this day's input from command-line values, which are either paths to input files
+    // ("-" meaning standard input) or, for argument-based days, the input values themselves. Files
+    // aren't read until the solution asks for their contents.
+    pub fn read_input(&self, values: &[String]) -> Result<Input, Box<dyn error::Error>> {
+        match self.input_kind {
+            InputKind::Arguments(_) => Ok(Input::new(values.to_vec())),
+            _ => {
+                if values.iter().filter(|&value| value == STDIN_PATH).count() > 1 {
+                    return Err("Only one input can be read from standard input".into());
+                }
+
+                Ok(Input::from_readers(
+                    values
+                        .iter()
+                        .map(|path| open(path))
+                        .collect::<io::Result<Vec<Box<dyn BufRead>>>>()?,
+                ))
+            }
+        }
+    }
+
+    pub fn usage(&self) -> String {
+        match self.input_kind {
+            InputKind::File => String::from("[INPUT_FILE_PATH]"),
+            InputKind::Files(suffixes) => suffixes
+                .iter()
+                .map(|suffix| format!("INPUT_FILE_{}_PATH", suffix.to_uppercase()))
+                .collect::<Vec<String>>()
+                .join(" "),
+            InputKind::Arguments(names) => names.join(" "),
+        }
+    }
+}
+
+pub const STDIN_PATH: &str = "-";
+
+fn open(path: &str) -> io::Result<Box<dyn BufRead>> {
+    if path == STDIN_PATH {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+fn parse_boxed<S: Solution + 'static>(
+    input: &Input,
+) -> Result<Box<dyn Solution>, Box<dyn error::Error>> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+pub static DAYS: [Day; 25] = [
+    Day { number: 1, input_kind: InputKind::File, parse: parse_boxed::<day01::Day01> },
+    Day { number: 2, input_kind: InputKind::File, parse: parse_boxed::<day02::Day02> },
+    Day { number: 3, input_kind: InputKind::File, parse: parse_boxed::<day03::Day03> },
+    Day { number: 4, input_kind: InputKind::File, parse: parse_boxed::<day04::Day04> },
+    Day { number: 5, input_kind: InputKind::File, parse: parse_boxed::<day05::Day05> },
+    Day { number: 6, input_kind: InputKind::File, parse: parse_boxed::<day06::Day06> },
+    Day { number: 7, input_kind: InputKind::File, parse: parse_boxed::<day07::Day07> },
+    Day { number: 8, input_kind: InputKind::File, parse: parse_boxed::<day08::Day08> },
+    Day { number: 9, input_kind: InputKind::File, parse: parse_boxed::<day09::Day09> },
+    Day { number: 10, input_kind: InputKind::File, parse: parse_boxed::<day10::Day10> },
+    Day { number: 11, input_kind: InputKind::File, parse: parse_boxed::<day11::Day11> },
+    Day { number: 12, input_kind: InputKind::File, parse: parse_boxed::<day12::Day12> },
+    Day { number: 13, input_kind: InputKind::File, parse: parse_boxed::<day13::Day13> },
+    Day { number: 14, input_kind: InputKind::File, parse: parse_boxed::<day14::Day14> },
+    Day { number: 15, input_kind: InputKind::File, parse: parse_boxed::<day15::Day15> },
+    Day { number: 16, input_kind: InputKind::File, parse: parse_boxed::<day16::Day16> },
+    Day { number: 17, input_kind: InputKind::File, parse: parse_boxed::<day17::Day17> },
+    Day { number: 18, input_kind: InputKind::File, parse: parse_boxed::<day18::Day18> },
+    Day { number: 19, input_kind: InputKind::File, parse: parse_boxed::<day19::Day19> },
+    Day { number: 20, input_kind: InputKind::File, parse: parse_boxed::<day20::Day20> },
+    Day {
+        number: 21,
+        input_kind: InputKind::Arguments(&["P1_START", "P2_START"]),
+        parse: parse_boxed::<day21::Day21>,
+    },
+    Day { number: 22, input_kind: InputKind::File, parse: parse_boxed::<day22::Day22> },
+    Day {
+        number: 23,
+        input_kind: InputKind::Files(&["a", "b"]),
+        parse: parse_boxed::<day23::Day23>,
+    },
+    Day { number: 24, input_kind: InputKind::File, parse: parse_boxed::<day24::Day24> },
+    Day { number: 25, input_kind: InputKind::File, parse: parse_boxed::<day25::Day25> },
+];
+
+pub fn solve(solution: &dyn Solution, part: u32) -> Result<String, Box<dyn error::Error>> {
+    match part {
+        1 => solution.part1(),
+        2 => solution.part2(),
+        _ => Err(format!("No part {}", part).into()),
+    }
+}
+
+pub fn day(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_day() {
+        assert_eq!(18, day(18).unwrap().number);
+        assert!(day(0).is_none());
+        assert!(day(26).is_none());
+    }
+
+    #[test]
+    fn test_default_paths() {
+        assert_eq!(
+            Some(vec![String::from("data/day01.txt")]),
+            day(1).unwrap().default_paths("data")
+        );
+
+        assert_eq!(
+            Some(vec![
+                String::from("data/day23-a.txt"),
+                String::from("data/day23-b.txt")
+            ]),
+            day(23).unwrap().default_paths("data")
+        );
+
+        assert_eq!(None, day(21).unwrap().default_paths("data"));
+        assert_eq!(
+            Some(vec![String::from("data/day24.txt")]),
+            day(24).unwrap().default_paths("data")
+        );
+    }
+
+    #[test]
+    fn test_input_value() {
+        let input = Input::new(vec![String::from("4"), String::from("8")]);
+
+        assert_eq!("4", input.text().unwrap());
+        assert_eq!("8", input.value(1).unwrap());
+        assert!(input.value(2).is_err());
+    }
+
+    #[test]
+    fn test_input_lines() {
+        let input = Input::from_readers(vec![Box::new("1\n2\n".as_bytes())]);
+
+        assert_eq!(
+            vec![String::from("1"), String::from("2")],
+            input.lines(0).unwrap().collect::<io::Result<Vec<String>>>().unwrap()
+        );
+
+        // Streamed input can only be read once...
+        assert!(input.text().is_err());
+
+        // ...unless it's been read into memory
+        let input = Input::from_readers(vec![Box::new("1\n2\n".as_bytes())]);
+
+        assert_eq!("1\n2\n", input.text().unwrap());
+        assert_eq!(2, input.lines(0).unwrap().count());
+        assert_eq!("1\n2\n", input.text().unwrap());
+    }
+}
@@ -0,0 +1,122 @@
+use advent_of_code_2021::solution::{self, Solution};
+
+const MANIFEST: &str = include_str!("answers.txt");
+const DATA_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+
+#[derive(Default)]
+struct Expectations {
+    arguments: Vec<String>,
+    answers: Vec<(u32, String)>,
+}
+
+fn expectations(day: u32) -> Expectations {
+    let mut expectations = Expectations::default();
+
+    for line in MANIFEST
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    {
+        let (line_day, rest) = line.split_once(' ').expect("Missing part");
+
+        if line_day.parse::<u32>().expect("Bad day number") != day {
+            continue;
+        }
+
+        let (part, answer) = rest.split_once(' ').expect("Missing answer");
+
+        match part {
+            "input" => {
+                expectations.arguments = answer.split_whitespace().map(String::from).collect()
+            }
+            _ => expectations.answers.push((
+                part.parse().expect("Bad part number"),
+                answer.replace("\\n", "\n"),
+            )),
+        }
+    }
+
+    expectations
+}
+
+fn check_answers(day_number: u32) {
+    let day = solution::day(day_number).unwrap();
+    let expectations = expectations(day_number);
+
+    assert!(
+        !expectations.answers.is_empty(),
+        "day{:02}: no expected answers in manifest",
+        day_number
+    );
+
+    let values = day
+        .default_paths(DATA_DIR)
+        .unwrap_or(expectations.arguments);
+
+    let solution: Box<dyn Solution> = day
+        .read_input(&values)
+        .and_then(|input| day.parse(&input))
+        .unwrap_or_else(|error| panic!("day{:02}: could not parse input: {}", day_number, error));
+
+    let drifted: Vec<String> = expectations
+        .answers
+        .iter()
+        .filter_map(|(part, expected)| {
+            let actual = match part {
+                1 => solution.part1(),
+                _ => solution.part2(),
+            };
+
+            match actual {
+                Ok(actual) if actual.trim_end() == expected.trim_end() => None,
+                Ok(actual) => Some(format!(
+                    "day{:02} part {}: expected {:?}, got {:?}",
+                    day_number, part, expected, actual
+                )),
+                Err(error) => Some(format!(
+                    "day{:02} part {}: expected {:?}, got error: {}",
+                    day_number, part, expected, error
+                )),
+            }
+        })
+        .collect();
+
+    assert!(drifted.is_empty(), "{}", drifted.join("\n"));
+}
+
+macro_rules! answer_tests {
+    ($($name:ident: $day:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check_answers($day);
+            }
+        )*
+    };
+}
+
+answer_tests! {
+    day01: 1,
+    day02: 2,
+    day03: 3,
+    day04: 4,
+    day05: 5,
+    day06: 6,
+    day07: 7,
+    day08: 8,
+    day09: 9,
+    day10: 10,
+    day11: 11,
+    day12: 12,
+    day13: 13,
+    day14: 14,
+    day15: 15,
+    day16: 16,
+    day17: 17,
+    day18: 18,
+    day19: 19,
+    day20: 20,
+    day22: 22,
+    day23: 23,
+    day24: 24,
+    day25: 25,
+}